@@ -0,0 +1,674 @@
+use std::path::{Path, PathBuf};
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountDeserialize;
+use anyhow::{anyhow, Result};
+use clap::{Parser, Subcommand};
+use fame_client::accounts::{self, BondingCurve, GlobalState, LiquidityPool, Order, TokenInfo};
+use fame_client::allowlist::Allowlist;
+use fame_client::instructions::{
+    CreateTokenArgs, CreatorAllocation, HoldingCap, HoldingLimit, LaunchProtection, Presale,
+    TradeSide,
+};
+use fame_client::quote::{self, Quoter};
+use fame_client::{instructions, pda};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::sysvar;
+use solana_sdk::transaction::Transaction;
+
+#[derive(Parser)]
+#[command(name = "fame-cli", about = "Operate the fame program")]
+struct Cli {
+    /// RPC endpoint, e.g. a local validator
+    #[arg(
+        long,
+        short = 'u',
+        global = true,
+        default_value = "http://127.0.0.1:8899"
+    )]
+    url: String,
+
+    /// Keypair file that signs and pays for transactions
+    #[arg(long, short = 'k', global = true, default_value = "keys/test.json")]
+    keypair: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create the global program state with the signer as admin
+    Initialize,
+    /// Create a new token on the bonding curve
+    CreateToken {
+        #[arg(long)]
+        name: String,
+
+        #[arg(long)]
+        symbol: String,
+
+        #[arg(long, default_value = "")]
+        social_account_url: String,
+
+        /// Lamports the creator spends on the first buy, in the same transaction
+        #[arg(long)]
+        initial_buy: Option<u64>,
+
+        /// Share of the max supply locked for the creator, in basis points, paid for at the
+        /// curve price
+        #[arg(long)]
+        allocation_bps: Option<u16>,
+
+        /// Seconds after creation before the allocation starts vesting
+        #[arg(long, default_value_t = 0, requires = "allocation_bps")]
+        cliff: i64,
+
+        /// Seconds after creation until the whole allocation has vested
+        #[arg(long, default_value_t = 0, requires = "allocation_bps")]
+        vesting_duration: i64,
+
+        /// Unix timestamp before which the curve rejects buys, defaults to right away
+        #[arg(long)]
+        launch_at: Option<i64>,
+
+        /// Buy fee at launch, in basis points, decaying to the protocol fee
+        #[arg(long, requires = "protection_slots")]
+        protection_fee_bps: Option<u16>,
+
+        /// Slots over which the launch buy fee decays
+        #[arg(long, requires = "protection_fee_bps")]
+        protection_slots: Option<u64>,
+
+        /// Burn the launch fee above the protocol fee instead of keeping it as creator fees
+        #[arg(long, requires = "protection_fee_bps")]
+        burn_launch_fee: bool,
+
+        /// Most tokens one wallet may buy into early on, as basis points of the max supply
+        #[arg(long, conflicts_with = "holding_cap")]
+        holding_cap_bps: Option<u16>,
+
+        /// Most tokens one wallet may buy into early on, as an absolute amount
+        #[arg(long)]
+        holding_cap: Option<u64>,
+
+        /// Supply at which the holding limit lifts
+        #[arg(long)]
+        holding_until_supply: Option<u64>,
+
+        /// Unix timestamp at which the holding limit lifts
+        #[arg(long)]
+        holding_until: Option<i64>,
+
+        /// File listing the presale wallets, one address per line
+        #[arg(long, requires_all = ["presale_cap", "presale_ends_at"])]
+        presale_allowlist: Option<PathBuf>,
+
+        /// Most tokens one wallet may buy into during the presale
+        #[arg(long, requires = "presale_allowlist")]
+        presale_cap: Option<u64>,
+
+        /// Unix timestamp at which the presale ends and public trading opens
+        #[arg(long, requires = "presale_allowlist")]
+        presale_ends_at: Option<i64>,
+    },
+    /// Claim the vested part of the creator allocation of a token
+    ClaimVested {
+        #[arg(long)]
+        mint: Pubkey,
+    },
+    /// Buy tokens for the given lamports
+    Buy {
+        #[arg(long)]
+        mint: Pubkey,
+
+        #[arg(long)]
+        lamports: u64,
+
+        /// Wallet credited with the tokens, defaults to the signer
+        #[arg(long)]
+        recipient: Option<Pubkey>,
+
+        /// Presale allowlist file, to prove the recipient is on it while the presale runs
+        #[arg(long)]
+        allowlist: Option<PathBuf>,
+
+        /// Wrapped SOL account of the signer to pay from instead of their lamports
+        #[arg(long, conflicts_with = "recipient")]
+        wsol: Option<Pubkey>,
+    },
+    /// Sell the given amount of base token units
+    Sell {
+        #[arg(long)]
+        mint: Pubkey,
+
+        #[arg(long)]
+        tokens: u64,
+
+        /// Wrapped SOL account to pay the proceeds into instead of the signer's lamports
+        #[arg(long)]
+        wsol: Option<Pubkey>,
+    },
+    /// Sell the signer's whole balance of a token
+    SellAll {
+        #[arg(long)]
+        mint: Pubkey,
+
+        /// Close the emptied token account and portfolio to refund their rent
+        #[arg(long)]
+        close: bool,
+    },
+    /// Quote a buy and a sell against the current curve state
+    Quote {
+        #[arg(long)]
+        mint: Pubkey,
+
+        #[arg(long, default_value_t = 0)]
+        lamports: u64,
+
+        #[arg(long, default_value_t = 0)]
+        tokens: u64,
+    },
+    /// Check that a token's liquidity pool can buy back the whole supply
+    Verify {
+        #[arg(long)]
+        mint: Pubkey,
+    },
+    /// Withdraw accumulated fees of a token's liquidity pool
+    WithdrawFees {
+        #[arg(long)]
+        mint: Pubkey,
+
+        #[arg(long)]
+        amount: u64,
+
+        /// Defaults to the signer
+        #[arg(long)]
+        receiver: Option<Pubkey>,
+    },
+    /// Pause trading, or resume it with --resume
+    Pause {
+        #[arg(long)]
+        resume: bool,
+    },
+    /// Set the buy and sell fees in basis points
+    SetFees {
+        #[arg(long)]
+        buy_bps: u16,
+
+        #[arg(long)]
+        sell_bps: u16,
+    },
+    /// Set the per-transaction and per-slot trade limits, 0 for none
+    SetTradeLimits {
+        /// Most lamports per buy
+        #[arg(long, default_value_t = 0)]
+        max_buy: u64,
+
+        /// Most tokens per sell
+        #[arg(long, default_value_t = 0)]
+        max_sell: u64,
+
+        /// Most lamports traded per token in one slot
+        #[arg(long, default_value_t = 0)]
+        max_slot_volume: u64,
+    },
+    /// Place a limit order that buys once the spot price falls to the trigger, or with --sell
+    /// sells once it rises to it
+    PlaceOrder {
+        #[arg(long)]
+        mint: Pubkey,
+
+        /// Number telling the signer's open orders on the mint apart
+        #[arg(long)]
+        id: u64,
+
+        #[arg(long)]
+        sell: bool,
+
+        /// Lamports to spend, or tokens to sell with --sell, escrowed until the order fills
+        #[arg(long)]
+        amount: u64,
+
+        /// Spot price in lamports per base token unit
+        #[arg(long)]
+        trigger_price: u64,
+
+        /// Fewest tokens a buy may mint, or fewest lamports a sell may pay out
+        #[arg(long, default_value_t = 0)]
+        limit: u64,
+    },
+    /// Cancel an order of the signer, returning its escrow
+    CancelOrder {
+        #[arg(long)]
+        mint: Pubkey,
+
+        #[arg(long)]
+        id: u64,
+    },
+    /// Fill a triggered order of any owner, earning the keeper reward
+    ExecuteOrder {
+        #[arg(long)]
+        owner: Pubkey,
+
+        #[arg(long)]
+        mint: Pubkey,
+
+        #[arg(long)]
+        id: u64,
+    },
+    /// Print the global state, or the state of a token with --mint
+    Show {
+        #[arg(long)]
+        mint: Option<Pubkey>,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let rpc = RpcClient::new_with_commitment(cli.url, CommitmentConfig::confirmed());
+    let signer = || {
+        read_keypair_file(&cli.keypair)
+            .map_err(|err| anyhow!("failed to read keypair {}: {err}", cli.keypair.display()))
+    };
+
+    match cli.command {
+        Command::Initialize => {
+            let admin = signer()?;
+            send(
+                &rpc,
+                &admin,
+                &[instructions::initialize(&admin.pubkey())],
+                &[],
+            )
+        }
+        Command::CreateToken {
+            name,
+            symbol,
+            social_account_url,
+            initial_buy,
+            allocation_bps,
+            cliff,
+            vesting_duration,
+            launch_at,
+            protection_fee_bps,
+            protection_slots,
+            burn_launch_fee,
+            holding_cap_bps,
+            holding_cap,
+            holding_until_supply,
+            holding_until,
+            presale_allowlist,
+            presale_cap,
+            presale_ends_at,
+        } => {
+            let creator = signer()?;
+            let mint = Keypair::new();
+            let allocation = allocation_bps.map(|supply_bps| CreatorAllocation {
+                supply_bps,
+                cliff_seconds: cliff,
+                duration_seconds: vesting_duration,
+            });
+            let protection =
+                protection_fee_bps
+                    .zip(protection_slots)
+                    .map(|(start_fee_bps, duration_slots)| LaunchProtection {
+                        start_fee_bps,
+                        duration_slots,
+                        burn_surplus: burn_launch_fee,
+                    });
+            let holding_limit = holding_cap_bps
+                .map(HoldingCap::MaxSupplyBps)
+                .or(holding_cap.map(HoldingCap::Amount))
+                .map(|cap| HoldingLimit {
+                    cap,
+                    until_supply: holding_until_supply,
+                    until_timestamp: holding_until,
+                });
+            let presale = match presale_allowlist {
+                Some(path) => Some(Presale {
+                    merkle_root: read_allowlist(&path)?.root(),
+                    max_per_wallet: presale_cap.unwrap_or_default(),
+                    ends_at: presale_ends_at.unwrap_or_default(),
+                }),
+                None => None,
+            };
+            let args = CreateTokenArgs {
+                name,
+                symbol,
+                social_account_url,
+                initial_buy_sol: initial_buy,
+                creator_allocation: allocation,
+                launch_at,
+                launch_protection: protection,
+                holding_limit,
+                presale,
+            };
+            let instruction = instructions::create_token(&creator.pubkey(), &mint.pubkey(), args);
+            send(&rpc, &creator, &[instruction], &[&mint])?;
+            println!("mint: {}", mint.pubkey());
+            Ok(())
+        }
+        Command::ClaimVested { mint } => {
+            let creator = signer()?;
+            let instruction = instructions::claim_vested(&creator.pubkey(), &mint);
+            send(&rpc, &creator, &[instruction], &[])
+        }
+        Command::Buy {
+            mint,
+            lamports,
+            recipient,
+            allowlist,
+            wsol,
+        } => {
+            let user = signer()?;
+            let recipient = recipient.unwrap_or(user.pubkey());
+            let proof = match allowlist {
+                Some(path) => read_allowlist(&path)?
+                    .proof(&recipient)
+                    .ok_or_else(|| anyhow!("{recipient} is not on the allowlist"))?,
+                None => Vec::new(),
+            };
+            let instruction = match wsol {
+                Some(wsol) => instructions::mint_token_from_wsol(
+                    &user.pubkey(),
+                    &mint,
+                    lamports,
+                    &wsol,
+                    proof,
+                ),
+                None => {
+                    instructions::mint_token_for(&user.pubkey(), &recipient, &mint, lamports, proof)
+                }
+            };
+            send(&rpc, &user, &[instruction], &[])
+        }
+        Command::Sell { mint, tokens, wsol } => {
+            let user = signer()?;
+            let instruction = match wsol {
+                Some(wsol) => {
+                    instructions::burn_token_to_wsol(&user.pubkey(), &mint, tokens, &wsol)
+                }
+                None => instructions::burn_token(&user.pubkey(), &mint, tokens),
+            };
+            send(&rpc, &user, &[instruction], &[])
+        }
+        Command::SellAll { mint, close } => {
+            let user = signer()?;
+            let instruction = instructions::burn_all(&user.pubkey(), &mint, close);
+            send(&rpc, &user, &[instruction], &[])
+        }
+        Command::Quote {
+            mint,
+            lamports,
+            tokens,
+        } => {
+            let quote = quoter(&rpc, &mint)?
+                .quote(lamports, tokens)
+                .map_err(|err| anyhow!("quote failed: {err}"))?;
+            println!("spot price:      {} lamports/unit", quote.spot_price);
+            println!("buy {lamports} lamports:");
+            match quote.buy {
+                Some(buy) => {
+                    println!("  tokens out:    {}", buy.amount_out);
+                    println!("  fee:           {}", buy.fee);
+                }
+                None => println!("  would fail"),
+            }
+            println!("sell {tokens} tokens:");
+            match quote.sell {
+                Some(sell) => {
+                    println!("  lamports out:  {}", sell.amount_out);
+                    println!("  fee:           {}", sell.fee);
+                }
+                None => println!("  would fail"),
+            }
+            Ok(())
+        }
+        Command::Verify { mint } => verify_pool(&rpc, &mint),
+        Command::WithdrawFees {
+            mint,
+            amount,
+            receiver,
+        } => {
+            let admin = signer()?;
+            let receiver = receiver.unwrap_or(admin.pubkey());
+            let instruction =
+                instructions::withdraw_fees(&admin.pubkey(), &mint, &receiver, amount);
+            send(&rpc, &admin, &[instruction], &[])
+        }
+        Command::Pause { resume } => {
+            let admin = signer()?;
+            let instruction = instructions::set_paused(&admin.pubkey(), !resume);
+            send(&rpc, &admin, &[instruction], &[])
+        }
+        Command::SetFees { buy_bps, sell_bps } => {
+            let admin = signer()?;
+            let instruction = instructions::set_fees(&admin.pubkey(), buy_bps, sell_bps);
+            send(&rpc, &admin, &[instruction], &[])
+        }
+        Command::SetTradeLimits {
+            max_buy,
+            max_sell,
+            max_slot_volume,
+        } => {
+            let admin = signer()?;
+            let instruction =
+                instructions::set_trade_limits(&admin.pubkey(), max_buy, max_sell, max_slot_volume);
+            send(&rpc, &admin, &[instruction], &[])
+        }
+        Command::PlaceOrder {
+            mint,
+            id,
+            sell,
+            amount,
+            trigger_price,
+            limit,
+        } => {
+            let owner = signer()?;
+            let side = if sell {
+                TradeSide::Sell
+            } else {
+                TradeSide::Buy
+            };
+            let instruction = instructions::place_order(
+                &owner.pubkey(),
+                &mint,
+                id,
+                side,
+                amount,
+                trigger_price,
+                limit,
+            );
+            send(&rpc, &owner, &[instruction], &[])
+        }
+        Command::CancelOrder { mint, id } => {
+            let owner = signer()?;
+            let order: Order = fetch(&rpc, &pda::order(&owner.pubkey(), &mint, id).0)?;
+            let instruction = instructions::cancel_order(&owner.pubkey(), &mint, id, order.side);
+            send(&rpc, &owner, &[instruction], &[])
+        }
+        Command::ExecuteOrder { owner, mint, id } => {
+            let keeper = signer()?;
+            let order: Order = fetch(&rpc, &pda::order(&owner, &mint, id).0)?;
+            let instruction =
+                instructions::execute_order(&keeper.pubkey(), &owner, &mint, id, order.side);
+            send(&rpc, &keeper, &[instruction], &[])
+        }
+        Command::Show { mint: None } => show_global_state(&rpc),
+        Command::Show { mint: Some(mint) } => show_token(&rpc, &mint),
+    }
+}
+
+fn send(
+    rpc: &RpcClient,
+    payer: &Keypair,
+    instructions: &[Instruction],
+    extra_signers: &[&Keypair],
+) -> Result<()> {
+    let mut signers = vec![payer];
+    signers.extend_from_slice(extra_signers);
+
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &signers,
+        rpc.get_latest_blockhash()?,
+    );
+    let signature = rpc.send_and_confirm_transaction(&transaction)?;
+    println!("signature: {signature}");
+    Ok(())
+}
+
+fn read_allowlist(path: &Path) -> Result<Allowlist> {
+    let wallets = std::fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            line.parse()
+                .map_err(|err| anyhow!("invalid address {line}: {err}"))
+        })
+        .collect::<Result<Vec<Pubkey>>>()?;
+    Ok(Allowlist::new(&wallets))
+}
+
+fn fetch<T: AccountDeserialize>(rpc: &RpcClient, address: &Pubkey) -> Result<T> {
+    let data = rpc.get_account_data(address)?;
+    accounts::decode(&data).map_err(|err| anyhow!("failed to decode account {address}: {err}"))
+}
+
+fn quoter(rpc: &RpcClient, mint: &Pubkey) -> Result<Quoter> {
+    let global_state: GlobalState = fetch(rpc, &pda::global_state().0)?;
+    let token_info: TokenInfo = fetch(rpc, &pda::token_info(mint).0)?;
+    let bonding_curve: BondingCurve = fetch(rpc, &pda::bonding_curve(mint).0)?;
+    let liquidity_pool: LiquidityPool = fetch(rpc, &pda::liquidity_pool(mint).0)?;
+    Ok(Quoter::new(
+        global_state,
+        bonding_curve,
+        &token_info,
+        &liquidity_pool,
+        &accounts::clock(&rpc.get_account_data(&sysvar::clock::ID)?)?,
+    ))
+}
+
+fn verify_pool(rpc: &RpcClient, mint: &Pubkey) -> Result<()> {
+    let token_info: TokenInfo = fetch(rpc, &pda::token_info(mint).0)?;
+    let bonding_curve: BondingCurve = fetch(rpc, &pda::bonding_curve(mint).0)?;
+    let address = pda::liquidity_pool(mint).0;
+    let account = rpc.get_account(&address)?;
+    let liquidity_pool: LiquidityPool = accounts::decode(&account.data)
+        .map_err(|err| anyhow!("failed to decode account {address}: {err}"))?;
+
+    // Same check as the verify_pool instruction
+    let report = quote::pool_report(
+        &bonding_curve,
+        token_info.total_supply,
+        &liquidity_pool,
+        account.lamports,
+        rpc.get_minimum_balance_for_rent_exemption(account.data.len())?,
+    )
+    .map_err(|err| anyhow!("verification failed: {err}"))?;
+    println!("liquidity pool {address}");
+    println!("  total supply:  {}", report.total_supply);
+    println!("  required:      {}", report.required_reserves);
+    println!("  balance:       {}", report.balance);
+    println!("  fees:          {}", report.accumulated_fees);
+    println!("  lamports:      {}", report.lamports);
+    println!("  rent reserve:  {}", report.rent_exempt_minimum);
+    println!("  solvent:       {}", report.solvent);
+    Ok(())
+}
+
+fn show_global_state(rpc: &RpcClient) -> Result<()> {
+    let address = pda::global_state().0;
+    let global_state: GlobalState = fetch(rpc, &address)?;
+    println!("global state {address}");
+    println!("  admin:         {}", global_state.admin);
+    println!("  paused:        {}", global_state.paused);
+    println!("  buy fee:       {} bps", global_state.buy_fee_bps);
+    println!("  sell fee:      {} bps", global_state.sell_fee_bps);
+    println!("  max buy:       {} lamports", global_state.max_buy_sol);
+    println!("  max sell:      {} tokens", global_state.max_sell_tokens);
+    println!("  max slot vol:  {} lamports", global_state.max_slot_volume);
+    Ok(())
+}
+
+fn show_token(rpc: &RpcClient, mint: &Pubkey) -> Result<()> {
+    let address = pda::token_info(mint).0;
+    let token_info: TokenInfo = fetch(rpc, &address)?;
+    println!("token info {address}");
+    println!("  mint:          {}", token_info.mint);
+    println!("  name:          {}", token_info.name);
+    println!("  symbol:        {}", token_info.symbol);
+    println!("  social url:    {}", token_info.social_account_url);
+    println!("  total supply:  {}", token_info.total_supply);
+    println!("  authority:     {}", token_info.authority);
+    println!("  launch at:     {}", token_info.launch_at);
+    if let Some(protection) = token_info.launch_protection {
+        println!(
+            "  launch fee:    {} bps over {} slots from slot {}{}",
+            protection.start_fee_bps,
+            protection.duration_slots,
+            token_info.launch_slot,
+            if protection.burn_surplus {
+                ", burned"
+            } else {
+                ""
+            }
+        );
+    }
+    if let Some(limit) = token_info.holding_limit {
+        let cap = match limit.cap {
+            HoldingCap::MaxSupplyBps(bps) => format!("{bps} bps of the max supply"),
+            HoldingCap::Amount(amount) => format!("{amount} tokens"),
+        };
+        let until: Vec<String> = [
+            limit.until_supply.map(|supply| format!("supply {supply}")),
+            limit
+                .until_timestamp
+                .map(|timestamp| format!("time {timestamp}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        println!("  holding cap:   {cap} until {}", until.join(" or "));
+    }
+    if let Some(presale) = token_info.presale {
+        println!(
+            "  presale:       until {}, at most {} tokens a wallet",
+            presale.ends_at, presale.max_per_wallet
+        );
+        let root: String = presale
+            .merkle_root
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        println!("  allowlist:     merkle root {root}");
+    }
+
+    let address = pda::bonding_curve(mint).0;
+    let bonding_curve: BondingCurve = fetch(rpc, &address)?;
+    println!("bonding curve {address}");
+    println!("  initial price: {}", bonding_curve.initial_price);
+    println!("  slope:         {}", bonding_curve.slope);
+    println!("  admin:         {}", bonding_curve.admin);
+    println!("  max supply:    {}", bonding_curve.max_supply);
+    println!("  allocated:     {}", bonding_curve.allocated_supply);
+    match bonding_curve.calculate_price(token_info.total_supply) {
+        Ok(price) => println!("  spot price:    {price} lamports/unit"),
+        Err(err) => println!("  spot price:    unavailable ({err})"),
+    }
+
+    let address = pda::liquidity_pool(mint).0;
+    let liquidity_pool: LiquidityPool = fetch(rpc, &address)?;
+    println!("liquidity pool {address}");
+    println!("  balance:       {}", liquidity_pool.balance);
+    println!("  fees:          {}", liquidity_pool.accumulated_fees);
+    println!("  lamports:      {}", rpc.get_balance(&address)?);
+    println!("  authority:     {}", liquidity_pool.authority);
+    Ok(())
+}
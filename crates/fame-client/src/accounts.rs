@@ -0,0 +1,56 @@
+//! Decoding of `fame` program accounts from raw account data.
+
+use anchor_lang::prelude::Clock;
+use anchor_lang::{AccountDeserialize, Result};
+
+pub use fame::state::{
+    BondingCurve, GlobalState, IntentNonces, LiquidityPool, Observation, Order, PriceObservations,
+    TokenInfo, UserPortfolio, Vesting,
+};
+
+/// Decodes account data, checking the discriminator of `T`.
+pub fn decode<T: AccountDeserialize>(mut data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut data)
+}
+
+pub fn token_info(data: &[u8]) -> Result<TokenInfo> {
+    decode(data)
+}
+
+pub fn bonding_curve(data: &[u8]) -> Result<BondingCurve> {
+    decode(data)
+}
+
+pub fn liquidity_pool(data: &[u8]) -> Result<LiquidityPool> {
+    decode(data)
+}
+
+pub fn user_portfolio(data: &[u8]) -> Result<UserPortfolio> {
+    decode(data)
+}
+
+pub fn global_state(data: &[u8]) -> Result<GlobalState> {
+    decode(data)
+}
+
+pub fn price_observations(data: &[u8]) -> Result<PriceObservations> {
+    decode(data)
+}
+
+pub fn vesting(data: &[u8]) -> Result<Vesting> {
+    decode(data)
+}
+
+pub fn order(data: &[u8]) -> Result<Order> {
+    decode(data)
+}
+
+pub fn intent_nonces(data: &[u8]) -> Result<IntentNonces> {
+    decode(data)
+}
+
+/// Decodes the clock sysvar, which quotes need for the launch rules.
+pub fn clock(data: &[u8]) -> Result<Clock> {
+    bincode::deserialize(data)
+        .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into())
+}
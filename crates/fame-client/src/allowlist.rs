@@ -0,0 +1,57 @@
+//! Merkle allowlists for presales, hashed the way `mint_token` verifies them.
+
+use anchor_lang::prelude::Pubkey;
+use fame::state::Presale;
+
+/// Merkle tree over a presale allowlist. The root goes into [`Presale::merkle_root`] and each
+/// wallet passes its [`Allowlist::proof`] to `mint_token` while the presale runs.
+pub struct Allowlist {
+    wallets: Vec<Pubkey>,
+    // Leaves first, the root last; an unpaired node moves up a level unchanged
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl Allowlist {
+    pub fn new(wallets: &[Pubkey]) -> Self {
+        let mut levels = vec![wallets.iter().map(Presale::leaf).collect::<Vec<_>>()];
+        while levels.last().unwrap().len() > 1 {
+            let level = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => Presale::node(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(level);
+        }
+        Self {
+            wallets: wallets.to_vec(),
+            levels,
+        }
+    }
+
+    /// Root of the tree, all zeroes for an empty allowlist.
+    pub fn root(&self) -> [u8; 32] {
+        self.levels
+            .last()
+            .and_then(|level| level.first())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Sibling hashes from the leaf of `wallet` up to the root, or `None` if it is not listed.
+    pub fn proof(&self, wallet: &Pubkey) -> Option<Vec<[u8; 32]>> {
+        let mut index = self.wallets.iter().position(|listed| listed == wallet)?;
+        let mut proof = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        Some(proof)
+    }
+}
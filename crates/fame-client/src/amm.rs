@@ -0,0 +1,249 @@
+//! Aggregator integration: the [`Amm`] interface routers quote and build swaps through, and its
+//! implementation over the curve of one token.
+//!
+//! A router keeps an [`Amm`] per market, fetches [`Amm::accounts_to_update`] and feeds them to
+//! [`Amm::update`] whenever they change, and quotes offline from then on. Lamports are traded
+//! under the native mint.
+
+use std::collections::HashMap;
+
+use anchor_lang::prelude::{Clock, Pubkey};
+use anchor_lang::solana_program::instruction::AccountMeta;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::Result;
+use anchor_spl::token::spl_token::native_mint;
+use fame::errors::ErrorCode;
+use fame::state::{fee_for, BondingCurve, GlobalState, LiquidityPool, TokenInfo};
+
+use crate::instructions::{self, TradeSide};
+use crate::quote::Quoter;
+use crate::{accounts, pda};
+
+/// Raw data of the accounts an [`Amm`] asked for, by address.
+pub type AccountMap = HashMap<Pubkey, Vec<u8>>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuoteParams {
+    pub amount: u64,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SwapQuote {
+    pub in_amount: u64,
+    pub out_amount: u64,
+    /// Fee charged on the trade, in `fee_mint`.
+    pub fee_amount: u64,
+    pub fee_mint: Pubkey,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapParams {
+    pub user: Pubkey,
+    pub source_mint: Pubkey,
+    pub destination_mint: Pubkey,
+    pub in_amount: u64,
+    /// The swap fails unless it returns at least this much.
+    pub minimum_out_amount: u64,
+}
+
+/// Instruction data and account metas of a swap, for the router to place into its route.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SwapAndAccountMetas {
+    pub program_id: Pubkey,
+    pub data: Vec<u8>,
+    pub account_metas: Vec<AccountMeta>,
+}
+
+pub trait Amm {
+    /// Address identifying the market.
+    fn key(&self) -> Pubkey;
+    fn label(&self) -> String;
+    fn program_id(&self) -> Pubkey;
+    /// Mints the market trades between.
+    fn reserve_mints(&self) -> Vec<Pubkey>;
+    /// Accounts [`Amm::update`] needs to quote.
+    fn accounts_to_update(&self) -> Vec<Pubkey>;
+    fn update(&mut self, accounts: &AccountMap) -> Result<()>;
+    fn quote(&self, params: &QuoteParams) -> Result<SwapQuote>;
+    fn swap_and_account_metas(&self, params: &SwapParams) -> Result<SwapAndAccountMetas>;
+}
+
+/// The market of one token against lamports, traded through the `swap` instruction.
+///
+/// Quotes apply the launch rules the program applies to every buyer. The holding limit depends on
+/// the buyer's balance and is left to the program; presale buys need a proof and are not quoted.
+pub struct FameAmm {
+    mint: Pubkey,
+    state: Option<MarketState>,
+}
+
+struct MarketState {
+    global_state: GlobalState,
+    token_info: TokenInfo,
+    bonding_curve: BondingCurve,
+    liquidity_pool: LiquidityPool,
+    clock: Clock,
+}
+
+impl FameAmm {
+    pub fn new(mint: Pubkey) -> Self {
+        Self { mint, state: None }
+    }
+
+    /// The market of the liquidity pool at `key`, from the pool's account data.
+    pub fn from_liquidity_pool(key: &Pubkey, data: &[u8]) -> Result<Self> {
+        let liquidity_pool = accounts::liquidity_pool(data)?;
+        if pda::liquidity_pool(&liquidity_pool.token).0 != *key {
+            return Err(ErrorCode::InvalidToken.into());
+        }
+        Ok(Self::new(liquidity_pool.token))
+    }
+
+    pub fn mint(&self) -> Pubkey {
+        self.mint
+    }
+
+    fn side(&self, input_mint: &Pubkey, output_mint: &Pubkey) -> Result<TradeSide> {
+        match (*input_mint, *output_mint) {
+            (input, output) if input == native_mint::ID && output == self.mint => {
+                Ok(TradeSide::Buy)
+            }
+            (input, output) if input == self.mint && output == native_mint::ID => {
+                Ok(TradeSide::Sell)
+            }
+            _ => Err(ErrorCode::InvalidToken.into()),
+        }
+    }
+
+    fn state(&self) -> Result<&MarketState> {
+        self.state
+            .as_ref()
+            .ok_or_else(|| anchor_lang::error::ErrorCode::AccountNotInitialized.into())
+    }
+}
+
+impl Amm for FameAmm {
+    fn key(&self) -> Pubkey {
+        pda::liquidity_pool(&self.mint).0
+    }
+
+    fn label(&self) -> String {
+        "fame".to_string()
+    }
+
+    fn program_id(&self) -> Pubkey {
+        fame::ID
+    }
+
+    fn reserve_mints(&self) -> Vec<Pubkey> {
+        vec![native_mint::ID, self.mint]
+    }
+
+    fn accounts_to_update(&self) -> Vec<Pubkey> {
+        vec![
+            pda::global_state().0,
+            pda::token_info(&self.mint).0,
+            pda::bonding_curve(&self.mint).0,
+            pda::liquidity_pool(&self.mint).0,
+            sysvar::clock::ID,
+        ]
+    }
+
+    fn update(&mut self, accounts: &AccountMap) -> Result<()> {
+        let keys = self.accounts_to_update();
+        let data = |index: usize| {
+            accounts
+                .get(&keys[index])
+                .map(Vec::as_slice)
+                .ok_or(anchor_lang::error::ErrorCode::AccountNotEnoughKeys)
+        };
+        self.state = Some(MarketState {
+            global_state: accounts::global_state(data(0)?)?,
+            token_info: accounts::token_info(data(1)?)?,
+            bonding_curve: accounts::bonding_curve(data(2)?)?,
+            liquidity_pool: accounts::liquidity_pool(data(3)?)?,
+            clock: accounts::clock(data(4)?)?,
+        });
+        Ok(())
+    }
+
+    fn quote(&self, params: &QuoteParams) -> Result<SwapQuote> {
+        let side = self.side(&params.input_mint, &params.output_mint)?;
+        let state = self.state()?;
+        let global_state = &state.global_state;
+        let token_info = &state.token_info;
+        let clock = &state.clock;
+        let quoter = Quoter::new(
+            global_state.clone(),
+            state.bonding_curve.clone(),
+            token_info,
+            &state.liquidity_pool,
+            clock,
+        );
+        let amount = params.amount;
+
+        if global_state.paused {
+            return Err(ErrorCode::TradingPaused.into());
+        }
+        match side {
+            TradeSide::Buy => {
+                if clock.unix_timestamp < token_info.launch_at {
+                    return Err(ErrorCode::NotLaunched.into());
+                }
+                if token_info
+                    .presale
+                    .is_some_and(|presale| clock.unix_timestamp < presale.ends_at)
+                {
+                    return Err(ErrorCode::NotAllowlisted.into());
+                }
+                if global_state.max_buy_sol != 0 && amount > global_state.max_buy_sol {
+                    return Err(ErrorCode::BuyLimitExceeded.into());
+                }
+                let out_amount = quoter.buy(amount)?;
+                if quoter.supply + out_amount > quoter.bonding_curve.max_supply {
+                    return Err(ErrorCode::MaxSupplyExceeded.into());
+                }
+                Ok(SwapQuote {
+                    in_amount: amount,
+                    out_amount,
+                    fee_amount: fee_for(amount, quoter.buy_fee_bps),
+                    fee_mint: native_mint::ID,
+                })
+            }
+            TradeSide::Sell => {
+                if global_state.max_sell_tokens != 0 && amount > global_state.max_sell_tokens {
+                    return Err(ErrorCode::SellLimitExceeded.into());
+                }
+                let sol_to_return = quoter.sell(amount)?;
+                if sol_to_return > quoter.pool_balance {
+                    return Err(ErrorCode::InsufficientLiquidity.into());
+                }
+                let fee_amount = global_state.sell_fee(sol_to_return);
+                Ok(SwapQuote {
+                    in_amount: amount,
+                    out_amount: sol_to_return - fee_amount,
+                    fee_amount,
+                    fee_mint: native_mint::ID,
+                })
+            }
+        }
+    }
+
+    fn swap_and_account_metas(&self, params: &SwapParams) -> Result<SwapAndAccountMetas> {
+        let side = self.side(&params.source_mint, &params.destination_mint)?;
+        let instruction = instructions::swap(
+            &params.user,
+            &self.mint,
+            params.in_amount,
+            params.minimum_out_amount,
+            side,
+        );
+        Ok(SwapAndAccountMetas {
+            program_id: instruction.program_id,
+            data: instruction.data,
+            account_metas: instruction.accounts,
+        })
+    }
+}
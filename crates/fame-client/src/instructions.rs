@@ -0,0 +1,574 @@
+//! Builders for every `fame` program instruction.
+//!
+//! Program-derived accounts are filled in from the mint and signer keys, so callers only pass
+//! the keys a transaction actually has to choose.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::{ed25519_program, incinerator, sysvar};
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::{associated_token, token};
+pub use fame::instructions::{CreatorAllocation, TradeIntent, TradeLeg, TradeSide};
+pub use fame::state::{HoldingCap, HoldingLimit, LaunchProtection, Presale};
+use fame::{accounts, instruction};
+
+use crate::pda;
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: fame::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub fn initialize(admin: &Pubkey) -> Instruction {
+    build(
+        accounts::Initialize {
+            admin: *admin,
+            global_state: pda::global_state().0,
+            system_program: system_program::ID,
+        },
+        instruction::Initialize {},
+    )
+}
+
+/// Arguments of `create_token`. The default creates a token that launches right away, without an
+/// initial buy, a creator allocation, launch protection, a holding limit or a presale.
+#[derive(Clone, Debug, Default)]
+pub struct CreateTokenArgs {
+    pub name: String,
+    pub symbol: String,
+    pub social_account_url: String,
+    /// Lamports the creator spends on a first buy in the same instruction.
+    pub initial_buy_sol: Option<u64>,
+    /// Part of the max supply locked for the creator in a vesting account, which the creator pays
+    /// the curve price for.
+    pub creator_allocation: Option<CreatorAllocation>,
+    /// Unix timestamp before which `mint_token` is rejected.
+    pub launch_at: Option<i64>,
+    /// Raised buy fee right after launch, decaying to the protocol fee.
+    pub launch_protection: Option<LaunchProtection>,
+    /// Most tokens one wallet may buy into until the supply or time it lifts at.
+    pub holding_limit: Option<HoldingLimit>,
+    /// Allowlisted phase from launch until public trading opens.
+    pub presale: Option<Presale>,
+}
+
+/// `mint` must be a fresh keypair that also signs the transaction.
+pub fn create_token(creator: &Pubkey, mint: &Pubkey, args: CreateTokenArgs) -> Instruction {
+    let vesting = args
+        .creator_allocation
+        .as_ref()
+        .map(|_| pda::vesting(mint).0);
+    build(
+        accounts::CreateToken {
+            creator: *creator,
+            global_state: pda::global_state().0,
+            mint: *mint,
+            creator_token_account: pda::token_account(creator, mint),
+            token_info: pda::token_info(mint).0,
+            bonding_curve: pda::bonding_curve(mint).0,
+            liquidity_pool: pda::liquidity_pool(mint).0,
+            price_observations: pda::price_observations(mint).0,
+            creator_portfolio: pda::user_portfolio(creator, mint).0,
+            vesting,
+            vesting_token_account: vesting.map(|vesting| pda::token_account(&vesting, mint)),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::CreateToken {
+            name: args.name,
+            symbol: args.symbol,
+            social_account_url: args.social_account_url,
+            initial_buy_sol: args.initial_buy_sol,
+            creator_allocation: args.creator_allocation,
+            launch_at: args.launch_at,
+            launch_protection: args.launch_protection,
+            holding_limit: args.holding_limit,
+            presale: args.presale,
+        },
+    )
+}
+
+/// Creates the associated token account of `owner` for `mint` unless it already exists.
+pub fn create_token_account(payer: &Pubkey, owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+        payer,
+        owner,
+        mint,
+        &token::ID,
+    )
+}
+
+pub fn mint_token(user: &Pubkey, mint: &Pubkey, amount_sol: u64) -> Instruction {
+    mint_token_with_proof(user, mint, amount_sol, Vec::new())
+}
+
+/// `mint_token` during a presale, with the user's proof from
+/// [`Allowlist::proof`](crate::allowlist::Allowlist::proof).
+pub fn mint_token_with_proof(
+    user: &Pubkey,
+    mint: &Pubkey,
+    amount_sol: u64,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    mint_token_for(user, user, mint, amount_sol, proof)
+}
+
+/// `mint_token` paid by `user` and credited to `recipient`, whose token account and portfolio are
+/// created if needed. During a presale `proof` is the recipient's.
+pub fn mint_token_for(
+    user: &Pubkey,
+    recipient: &Pubkey,
+    mint: &Pubkey,
+    amount_sol: u64,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    build(
+        mint_token_accounts(user, recipient, mint),
+        instruction::MintToken { amount_sol, proof },
+    )
+}
+
+/// `mint_token` paid out of the user's wrapped SOL account `wsol_account` rather than their
+/// lamports. The user still funds the rent of the temporary unwrap account, refunded right away.
+/// During a presale `proof` is the user's.
+pub fn mint_token_from_wsol(
+    user: &Pubkey,
+    mint: &Pubkey,
+    amount_sol: u64,
+    wsol_account: &Pubkey,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    let mut accounts = mint_token_accounts(user, user, mint);
+    accounts.wsol_account = Some(*wsol_account);
+    accounts.wsol_unwrap = Some(pda::wsol_unwrap(user).0);
+    accounts.native_mint = Some(native_mint::ID);
+    build(accounts, instruction::MintToken { amount_sol, proof })
+}
+
+fn mint_token_accounts(user: &Pubkey, recipient: &Pubkey, mint: &Pubkey) -> accounts::MintToken {
+    accounts::MintToken {
+        user: *user,
+        recipient: *recipient,
+        global_state: pda::global_state().0,
+        token_info: pda::token_info(mint).0,
+        bonding_curve: pda::bonding_curve(mint).0,
+        liquidity_pool: pda::liquidity_pool(mint).0,
+        user_portfolio: pda::user_portfolio(recipient, mint).0,
+        mint: *mint,
+        user_token_account: pda::token_account(recipient, mint),
+        price_observations: pda::price_observations(mint).0,
+        incinerator: incinerator::ID,
+        wsol_account: None,
+        wsol_unwrap: None,
+        native_mint: None,
+        token_program: token::ID,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+    }
+}
+
+pub fn burn_token(user: &Pubkey, mint: &Pubkey, amount_tokens: u64) -> Instruction {
+    build(
+        burn_token_accounts(user, mint),
+        instruction::BurnToken { amount_tokens },
+    )
+}
+
+/// `burn_token` paying the proceeds into the wrapped SOL account `wsol_account`.
+pub fn burn_token_to_wsol(
+    user: &Pubkey,
+    mint: &Pubkey,
+    amount_tokens: u64,
+    wsol_account: &Pubkey,
+) -> Instruction {
+    let mut accounts = burn_token_accounts(user, mint);
+    accounts.wsol_account = Some(*wsol_account);
+    build(accounts, instruction::BurnToken { amount_tokens })
+}
+
+/// Sells the user's whole token balance; `close_accounts` also closes their token account and
+/// portfolio to refund the rent, and fails if the portfolio still counts tokens that left the
+/// account.
+pub fn burn_all(user: &Pubkey, mint: &Pubkey, close_accounts: bool) -> Instruction {
+    build(
+        burn_token_accounts(user, mint),
+        instruction::BurnAll { close_accounts },
+    )
+}
+
+fn burn_token_accounts(user: &Pubkey, mint: &Pubkey) -> accounts::BurnToken {
+    accounts::BurnToken {
+        user: *user,
+        global_state: pda::global_state().0,
+        token_info: pda::token_info(mint).0,
+        bonding_curve: pda::bonding_curve(mint).0,
+        liquidity_pool: pda::liquidity_pool(mint).0,
+        user_portfolio: pda::user_portfolio(user, mint).0,
+        mint: *mint,
+        user_token_account: pda::token_account(user, mint),
+        price_observations: pda::price_observations(mint).0,
+        wsol_account: None,
+        token_program: token::ID,
+        system_program: system_program::ID,
+    }
+}
+
+/// Trades `legs` in order in one instruction, failing as a whole if any leg falls short of its
+/// limit. The user's token account of every leg's mint must exist; send
+/// [`create_token_account`] ahead for new tokens.
+pub fn batch_trade(user: &Pubkey, legs: Vec<TradeLeg>) -> Instruction {
+    let leg_accounts: Vec<AccountMeta> = legs
+        .iter()
+        .flat_map(|leg| {
+            let mint = &leg.mint;
+            [
+                AccountMeta::new(pda::token_info(mint).0, false),
+                AccountMeta::new_readonly(pda::bonding_curve(mint).0, false),
+                AccountMeta::new(pda::liquidity_pool(mint).0, false),
+                AccountMeta::new(pda::user_portfolio(user, mint).0, false),
+                AccountMeta::new(*mint, false),
+                AccountMeta::new(pda::token_account(user, mint), false),
+                AccountMeta::new(pda::price_observations(mint).0, false),
+            ]
+        })
+        .collect();
+    let mut instruction = build(
+        accounts::BatchTrade {
+            user: *user,
+            global_state: pda::global_state().0,
+            incinerator: incinerator::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::BatchTrade { legs },
+    );
+    instruction.accounts.extend(leg_accounts);
+    instruction
+}
+
+/// The aggregator entrypoint: a buy spends `amount_in` lamports, a sell burns `amount_in` tokens,
+/// and either fails unless it returns at least `min_amount_out`. The user's token account and
+/// portfolio are created on a first buy.
+pub fn swap(
+    user: &Pubkey,
+    mint: &Pubkey,
+    amount_in: u64,
+    min_amount_out: u64,
+    side: TradeSide,
+) -> Instruction {
+    build(
+        accounts::Swap {
+            user: *user,
+            global_state: pda::global_state().0,
+            token_info: pda::token_info(mint).0,
+            bonding_curve: pda::bonding_curve(mint).0,
+            liquidity_pool: pda::liquidity_pool(mint).0,
+            user_portfolio: pda::user_portfolio(user, mint).0,
+            mint: *mint,
+            user_token_account: pda::token_account(user, mint),
+            price_observations: pda::price_observations(mint).0,
+            incinerator: incinerator::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::Swap {
+            amount_in,
+            min_amount_out,
+            side,
+        },
+    )
+}
+
+/// Approves the user's intent delegate to trade up to `amount` out of `token_account`: their
+/// wrapped SOL account for buys, or their token account of a mint for sells.
+pub fn approve_intent_delegate(user: &Pubkey, token_account: &Pubkey, amount: u64) -> Instruction {
+    token::spl_token::instruction::approve(
+        &token::ID,
+        token_account,
+        &pda::intent_nonces(user).0,
+        user,
+        &[],
+        amount,
+    )
+    .expect("valid approve instruction")
+}
+
+/// The Ed25519 program's check of the user's `signature` of
+/// [`TradeIntent::message`], which must come right before [`execute_intent`].
+pub fn intent_signature(intent: &TradeIntent, signature: &[u8; 64]) -> Instruction {
+    // One signature, then its offsets: the key, signature and message all follow in this data
+    const DATA_START: u16 = 16;
+    let message = intent.message();
+    let public_key_offset = DATA_START;
+    let signature_offset = public_key_offset + 32;
+    let message_offset = signature_offset + 64;
+    let mut data = vec![1, 0];
+    for value in [
+        signature_offset,
+        u16::MAX,
+        public_key_offset,
+        u16::MAX,
+        message_offset,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(intent.user.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(&message);
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: Vec::new(),
+        data,
+    }
+}
+
+/// Executes an intent the user signed, with `relayer` paying the fees. Buys pay from the user's
+/// wrapped SOL associated token account.
+pub fn execute_intent(relayer: &Pubkey, intent: TradeIntent) -> Instruction {
+    let user = &intent.user;
+    let mint = &intent.mint;
+    let buy = intent.side == TradeSide::Buy;
+    build(
+        accounts::ExecuteIntent {
+            relayer: *relayer,
+            user: *user,
+            intent_nonces: pda::intent_nonces(user).0,
+            global_state: pda::global_state().0,
+            token_info: pda::token_info(mint).0,
+            bonding_curve: pda::bonding_curve(mint).0,
+            liquidity_pool: pda::liquidity_pool(mint).0,
+            user_portfolio: pda::user_portfolio(user, mint).0,
+            mint: *mint,
+            user_token_account: pda::token_account(user, mint),
+            price_observations: pda::price_observations(mint).0,
+            incinerator: incinerator::ID,
+            wsol_account: buy.then(|| pda::token_account(user, &native_mint::ID)),
+            wsol_unwrap: buy.then(|| pda::wsol_unwrap(user).0),
+            native_mint: buy.then_some(native_mint::ID),
+            instructions: sysvar::instructions::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ExecuteIntent { intent },
+    )
+}
+
+/// Places limit order `id`, escrowing `amount` lamports for a buy or `amount` tokens for a sell
+/// until the spot price reaches `trigger_price`. `id` only has to be unique among the owner's open
+/// orders on the mint.
+pub fn place_order(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    id: u64,
+    side: TradeSide,
+    amount: u64,
+    trigger_price: u64,
+    limit: u64,
+) -> Instruction {
+    let order = pda::order(owner, mint, id).0;
+    build(
+        accounts::PlaceOrder {
+            owner: *owner,
+            token_info: pda::token_info(mint).0,
+            mint: *mint,
+            order,
+            order_token_account: (side == TradeSide::Sell)
+                .then(|| pda::token_account(&order, mint)),
+            user_portfolio: pda::user_portfolio(owner, mint).0,
+            user_token_account: pda::token_account(owner, mint),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::PlaceOrder {
+            id,
+            side,
+            amount,
+            trigger_price,
+            limit,
+        },
+    )
+}
+
+/// Cancels an order of `side`, returning its escrow to the owner.
+pub fn cancel_order(owner: &Pubkey, mint: &Pubkey, id: u64, side: TradeSide) -> Instruction {
+    let order = pda::order(owner, mint, id).0;
+    let sell = side == TradeSide::Sell;
+    build(
+        accounts::CancelOrder {
+            owner: *owner,
+            order,
+            order_token_account: sell.then(|| pda::token_account(&order, mint)),
+            user_token_account: sell.then(|| pda::token_account(owner, mint)),
+            token_program: token::ID,
+        },
+        instruction::CancelOrder {},
+    )
+}
+
+/// Fills a triggered order of `side` for `keeper`, who earns the keeper reward and fronts the
+/// lamports of a buy until the escrow repays them.
+pub fn execute_order(
+    keeper: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    id: u64,
+    side: TradeSide,
+) -> Instruction {
+    let order = pda::order(owner, mint, id).0;
+    build(
+        accounts::ExecuteOrder {
+            keeper: *keeper,
+            owner: *owner,
+            order,
+            order_token_account: (side == TradeSide::Sell)
+                .then(|| pda::token_account(&order, mint)),
+            global_state: pda::global_state().0,
+            token_info: pda::token_info(mint).0,
+            bonding_curve: pda::bonding_curve(mint).0,
+            liquidity_pool: pda::liquidity_pool(mint).0,
+            user_portfolio: pda::user_portfolio(owner, mint).0,
+            mint: *mint,
+            user_token_account: pda::token_account(owner, mint),
+            price_observations: pda::price_observations(mint).0,
+            incinerator: incinerator::ID,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ExecuteOrder {},
+    )
+}
+
+pub fn withdraw_fees(
+    admin: &Pubkey,
+    mint: &Pubkey,
+    fee_receiver: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::WithdrawFees {
+            admin: *admin,
+            liquidity_pool: pda::liquidity_pool(mint).0,
+            fee_receiver: *fee_receiver,
+            system_program: system_program::ID,
+        },
+        instruction::WithdrawFees { amount },
+    )
+}
+
+/// View instruction; the TWAP is returned as transaction return data.
+pub fn get_twap(mint: &Pubkey, window_seconds: u32) -> Instruction {
+    build(
+        accounts::GetTwap {
+            token_info: pda::token_info(mint).0,
+            bonding_curve: pda::bonding_curve(mint).0,
+            price_observations: pda::price_observations(mint).0,
+            mint: *mint,
+        },
+        instruction::GetTwap { window_seconds },
+    )
+}
+
+/// View instruction; the [`fame::instructions::Quote`] is returned as transaction return data.
+pub fn get_quote(mint: &Pubkey, amount_sol: u64, amount_tokens: u64) -> Instruction {
+    build(
+        accounts::GetQuote {
+            global_state: pda::global_state().0,
+            token_info: pda::token_info(mint).0,
+            bonding_curve: pda::bonding_curve(mint).0,
+            liquidity_pool: pda::liquidity_pool(mint).0,
+            mint: *mint,
+        },
+        instruction::GetQuote {
+            amount_sol,
+            amount_tokens,
+        },
+    )
+}
+
+/// View instruction; the [`fame::instructions::PoolReport`] is returned as transaction return
+/// data. Anyone may send it.
+pub fn verify_pool(mint: &Pubkey) -> Instruction {
+    build(
+        accounts::VerifyPool {
+            token_info: pda::token_info(mint).0,
+            bonding_curve: pda::bonding_curve(mint).0,
+            liquidity_pool: pda::liquidity_pool(mint).0,
+            mint: *mint,
+        },
+        instruction::VerifyPool {},
+    )
+}
+
+/// Releases the vested part of the creator allocation of `mint` to `beneficiary`.
+pub fn claim_vested(beneficiary: &Pubkey, mint: &Pubkey) -> Instruction {
+    let vesting = pda::vesting(mint).0;
+    build(
+        accounts::ClaimVested {
+            beneficiary: *beneficiary,
+            vesting,
+            vesting_token_account: pda::token_account(&vesting, mint),
+            beneficiary_token_account: pda::token_account(beneficiary, mint),
+            beneficiary_portfolio: pda::user_portfolio(beneficiary, mint).0,
+            mint: *mint,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimVested {},
+    )
+}
+
+pub fn set_paused(admin: &Pubkey, paused: bool) -> Instruction {
+    build(
+        accounts::SetPaused {
+            admin: *admin,
+            global_state: pda::global_state().0,
+        },
+        instruction::SetPaused { paused },
+    )
+}
+
+pub fn set_fees(admin: &Pubkey, buy_fee_bps: u16, sell_fee_bps: u16) -> Instruction {
+    build(
+        accounts::SetFees {
+            admin: *admin,
+            global_state: pda::global_state().0,
+        },
+        instruction::SetFees {
+            buy_fee_bps,
+            sell_fee_bps,
+        },
+    )
+}
+
+/// Limits of 0 lift the corresponding check.
+pub fn set_trade_limits(
+    admin: &Pubkey,
+    max_buy_sol: u64,
+    max_sell_tokens: u64,
+    max_slot_volume: u64,
+) -> Instruction {
+    build(
+        accounts::SetTradeLimits {
+            admin: *admin,
+            global_state: pda::global_state().0,
+        },
+        instruction::SetTradeLimits {
+            max_buy_sol,
+            max_sell_tokens,
+            max_slot_volume,
+        },
+    )
+}
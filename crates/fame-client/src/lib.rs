@@ -0,0 +1,12 @@
+//! Client helpers for the `fame` program: PDA derivation, instruction builders, account
+//! decoding, presale allowlists, an off-chain quoter that runs the on-chain curve math and an
+//! aggregator-style [`amm::Amm`] over it.
+
+pub mod accounts;
+pub mod allowlist;
+pub mod amm;
+pub mod instructions;
+pub mod pda;
+pub mod quote;
+
+pub use fame::ID as PROGRAM_ID;
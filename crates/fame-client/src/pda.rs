@@ -0,0 +1,58 @@
+//! Addresses of the accounts owned by the `fame` program.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+
+pub fn global_state() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"program_state"], &fame::ID)
+}
+
+pub fn token_info(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"token_info", mint.as_ref()], &fame::ID)
+}
+
+pub fn bonding_curve(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"bonding_curve", mint.as_ref()], &fame::ID)
+}
+
+pub fn liquidity_pool(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"liquidity_pool", mint.as_ref()], &fame::ID)
+}
+
+pub fn price_observations(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"price_observations", mint.as_ref()], &fame::ID)
+}
+
+pub fn user_portfolio(user: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"user_portfolio", user.as_ref(), mint.as_ref()],
+        &fame::ID,
+    )
+}
+
+/// Temporary account `mint_token` unwraps a wrapped SOL payment through.
+pub fn wsol_unwrap(user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"wsol_unwrap", user.as_ref()], &fame::ID)
+}
+
+/// Nonces of a user's signed intents, and the delegate the intents trade through.
+pub fn intent_nonces(user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"intent_nonces", user.as_ref()], &fame::ID)
+}
+
+/// Limit order `id` of `owner` on `mint`.
+pub fn order(owner: &Pubkey, mint: &Pubkey, id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"order", owner.as_ref(), mint.as_ref(), &id.to_le_bytes()],
+        &fame::ID,
+    )
+}
+
+pub fn vesting(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vesting", mint.as_ref()], &fame::ID)
+}
+
+/// The associated token account of `owner` for `mint`.
+pub fn token_account(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(owner, mint)
+}
@@ -0,0 +1,185 @@
+//! Off-chain quotes and pool checks computed with the program's own curve math.
+
+use anchor_lang::prelude::Clock;
+use anchor_lang::Result;
+use fame::instructions::{calculate_sol_to_return, calculate_tokens_to_mint};
+use fame::state::{fee_for, BondingCurve, GlobalState, LiquidityPool, TokenInfo};
+
+pub use fame::instructions::{pool_report, PoolReport, Quote, SideQuote};
+
+/// Snapshot of the curve state of one token, as read from its accounts.
+pub struct Quoter {
+    pub global_state: GlobalState,
+    /// Buy fee of a public buy at the clock of the snapshot, raised during the token's launch
+    /// protection.
+    pub buy_fee_bps: u16,
+    pub bonding_curve: BondingCurve,
+    pub supply: u64,
+    pub pool_balance: u64,
+}
+
+impl Quoter {
+    pub fn new(
+        global_state: GlobalState,
+        bonding_curve: BondingCurve,
+        token_info: &TokenInfo,
+        liquidity_pool: &LiquidityPool,
+        clock: &Clock,
+    ) -> Self {
+        Self {
+            buy_fee_bps: token_info.buy_fee_bps(
+                global_state.buy_fee_bps,
+                clock.slot,
+                clock.unix_timestamp,
+            ),
+            global_state,
+            bonding_curve,
+            supply: token_info.total_supply,
+            pool_balance: liquidity_pool.balance,
+        }
+    }
+
+    /// Spot price in lamports per base token unit.
+    pub fn spot_price(&self) -> Result<u64> {
+        self.bonding_curve.calculate_price(self.supply)
+    }
+
+    /// Tokens minted by `mint_token(amount_sol)`, which prices what is left after the fee.
+    pub fn buy(&self, amount_sol: u64) -> Result<u64> {
+        let fee = fee_for(amount_sol, self.buy_fee_bps);
+        calculate_tokens_to_mint(&self.bonding_curve, self.supply, amount_sol - fee)
+    }
+
+    /// Gross lamports released by `burn_token(amount_tokens)`, before the fee.
+    pub fn sell(&self, amount_tokens: u64) -> Result<u64> {
+        calculate_sol_to_return(&self.bonding_curve, self.supply, amount_tokens)
+    }
+
+    /// The same result the `get_quote` instruction would return.
+    pub fn quote(&self, amount_sol: u64, amount_tokens: u64) -> Result<Quote> {
+        fame::instructions::quote(
+            &self.global_state,
+            self.buy_fee_bps,
+            &self.bonding_curve,
+            self.supply,
+            self.pool_balance,
+            amount_sol,
+            amount_tokens,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+    use fame::state::{LaunchProtection, Presale};
+
+    use super::*;
+
+    const START_FEE_BPS: u16 = 5_000;
+
+    fn token_info(launch_slot: u64, presale: Option<Presale>) -> TokenInfo {
+        TokenInfo {
+            mint: Pubkey::new_unique(),
+            name: "Fame".to_string(),
+            symbol: "FAME".to_string(),
+            social_account_url: String::new(),
+            total_supply: 0,
+            authority: Pubkey::default(),
+            launch_at: 0,
+            launch_protection: Some(LaunchProtection {
+                start_fee_bps: START_FEE_BPS,
+                duration_slots: 100,
+                burn_surplus: false,
+            }),
+            launch_slot,
+            holding_limit: None,
+            presale,
+        }
+    }
+
+    fn quoter(token_info: &TokenInfo, slot: u64, unix_timestamp: i64) -> Quoter {
+        let global_state = GlobalState {
+            admin: Pubkey::default(),
+            paused: false,
+            buy_fee_bps: GlobalState::DEFAULT_FEE_BPS,
+            sell_fee_bps: GlobalState::DEFAULT_FEE_BPS,
+            max_buy_sol: 0,
+            max_sell_tokens: 0,
+            max_slot_volume: 0,
+        };
+        let bonding_curve = BondingCurve {
+            token: token_info.mint,
+            initial_price: 10_000_000,
+            slope: 92,
+            admin: Pubkey::default(),
+            max_supply: BondingCurve::DEFAULT_MAX_SUPPLY,
+            allocated_supply: 0,
+        };
+        let liquidity_pool = LiquidityPool {
+            token: token_info.mint,
+            balance: 0,
+            accumulated_fees: 0,
+            authority: Pubkey::default(),
+            volume_slot: 0,
+            slot_volume: 0,
+        };
+        let clock = Clock {
+            slot,
+            unix_timestamp,
+            ..Clock::default()
+        };
+        Quoter::new(
+            global_state,
+            bonding_curve,
+            token_info,
+            &liquidity_pool,
+            &clock,
+        )
+    }
+
+    #[test]
+    fn quotes_the_start_fee_until_the_window_opens() {
+        // However late, the first public buy opens the window and pays the start fee
+        let quoter = quoter(&token_info(0, None), 10_000, 0);
+        assert_eq!(quoter.buy_fee_bps, START_FEE_BPS);
+        let amount_to_pool = LAMPORTS_PER_SOL - fee_for(LAMPORTS_PER_SOL, START_FEE_BPS);
+        assert_eq!(
+            quoter.buy(LAMPORTS_PER_SOL).unwrap(),
+            calculate_tokens_to_mint(&quoter.bonding_curve, 0, amount_to_pool).unwrap()
+        );
+        let quote = quoter.quote(LAMPORTS_PER_SOL, 0).unwrap();
+        assert_eq!(
+            quote.buy.unwrap().fee,
+            fee_for(LAMPORTS_PER_SOL, START_FEE_BPS)
+        );
+    }
+
+    #[test]
+    fn quotes_the_fee_decaying_from_the_opened_window() {
+        let token_info = token_info(1_000, None);
+        let base_fee_bps = GlobalState::DEFAULT_FEE_BPS;
+        assert_eq!(quoter(&token_info, 1_000, 0).buy_fee_bps, START_FEE_BPS);
+        assert_eq!(
+            quoter(&token_info, 1_050, 0).buy_fee_bps,
+            START_FEE_BPS - (START_FEE_BPS - base_fee_bps) / 2
+        );
+        assert_eq!(quoter(&token_info, 1_100, 0).buy_fee_bps, base_fee_bps);
+    }
+
+    #[test]
+    fn quotes_the_protocol_fee_during_the_presale() {
+        let presale = Presale {
+            merkle_root: [0; 32],
+            max_per_wallet: 100,
+            ends_at: 60,
+        };
+        let token_info = token_info(0, Some(presale));
+        assert_eq!(
+            quoter(&token_info, 10_000, 59).buy_fee_bps,
+            GlobalState::DEFAULT_FEE_BPS
+        );
+        assert_eq!(quoter(&token_info, 10_000, 60).buy_fee_bps, START_FEE_BPS);
+    }
+}
@@ -0,0 +1,375 @@
+//! OHLCV candle aggregation over `TokenMinted`/`TokenBurned` trades.
+//!
+//! Prices are execution prices in lamports per base token unit, the same unit the bonding
+//! curve is quoted in. Both sides are priced by what the curve took in or paid out, without the
+//! fees, so that buys and sells in the same candle are comparable. Trades may be inserted in
+//! any order: a late trade lands in its own bucket and only moves that bucket's open/close if
+//! it is earlier/later than what is already there, so the aggregate always matches a replay in
+//! chronological order.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Interval {
+    OneMinute,
+    FiveMinutes,
+    OneHour,
+    OneDay,
+}
+
+impl Interval {
+    pub const ALL: [Interval; 4] = [
+        Interval::OneMinute,
+        Interval::FiveMinutes,
+        Interval::OneHour,
+        Interval::OneDay,
+    ];
+
+    pub fn seconds(self) -> i64 {
+        match self {
+            Interval::OneMinute => 60,
+            Interval::FiveMinutes => 5 * 60,
+            Interval::OneHour => 60 * 60,
+            Interval::OneDay => 24 * 60 * 60,
+        }
+    }
+
+    /// Start of the bucket containing `timestamp`.
+    pub fn bucket_start(self, timestamp: i64) -> i64 {
+        timestamp - timestamp.rem_euclid(self.seconds())
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Interval::OneMinute => "1m",
+            Interval::FiveMinutes => "5m",
+            Interval::OneHour => "1h",
+            Interval::OneDay => "1d",
+        })
+    }
+}
+
+impl FromStr for Interval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Interval::ALL
+            .into_iter()
+            .find(|interval| interval.to_string() == s)
+            .ok_or_else(|| format!("unknown interval `{s}`, expected one of 1m, 5m, 1h, 1d"))
+    }
+}
+
+/// A single buy or sell against the curve.
+pub struct Trade {
+    pub mint: Pubkey,
+    /// Unix timestamp of the block the trade landed in.
+    pub timestamp: i64,
+    /// Orders trades sharing a timestamp, e.g. `(slot, event index)`.
+    pub sequence: (u64, u64),
+    pub token_amount: u64,
+    /// Lamports the curve took in or paid out, without fees.
+    pub sol_amount: u64,
+}
+
+impl Trade {
+    /// Execution price in lamports per base token unit.
+    pub fn price(&self) -> f64 {
+        self.sol_amount as f64 / self.token_amount as f64
+    }
+
+    fn order_key(&self) -> (i64, u64, u64) {
+        (self.timestamp, self.sequence.0, self.sequence.1)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Candle {
+    pub start: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume_sol: u64,
+    pub volume_tokens: u64,
+    /// Zero for gap-filled candles.
+    pub trades: u64,
+}
+
+struct Bucket {
+    open_key: (i64, u64, u64),
+    open: f64,
+    close_key: (i64, u64, u64),
+    close: f64,
+    high: f64,
+    low: f64,
+    volume_sol: u64,
+    volume_tokens: u64,
+    trades: u64,
+}
+
+impl Bucket {
+    fn new(trade: &Trade) -> Self {
+        let price = trade.price();
+        Self {
+            open_key: trade.order_key(),
+            open: price,
+            close_key: trade.order_key(),
+            close: price,
+            high: price,
+            low: price,
+            volume_sol: trade.sol_amount,
+            volume_tokens: trade.token_amount,
+            trades: 1,
+        }
+    }
+
+    fn add(&mut self, trade: &Trade) {
+        let price = trade.price();
+        let key = trade.order_key();
+        if key < self.open_key {
+            self.open_key = key;
+            self.open = price;
+        }
+        if key >= self.close_key {
+            self.close_key = key;
+            self.close = price;
+        }
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.volume_sol = self.volume_sol.saturating_add(trade.sol_amount);
+        self.volume_tokens = self.volume_tokens.saturating_add(trade.token_amount);
+        self.trades += 1;
+    }
+
+    fn candle(&self, start: i64) -> Candle {
+        Candle {
+            start,
+            open: self.open,
+            high: self.high,
+            low: self.low,
+            close: self.close,
+            volume_sol: self.volume_sol,
+            volume_tokens: self.volume_tokens,
+            trades: self.trades,
+        }
+    }
+}
+
+/// Aggregates trades into candles for every [`Interval`] and mint.
+///
+/// Each trade must be inserted exactly once; deduplication is the caller's job (the indexer
+/// store already guarantees it per signature).
+#[derive(Default)]
+pub struct CandleAggregator {
+    series: HashMap<(Pubkey, Interval), BTreeMap<i64, Bucket>>,
+}
+
+impl CandleAggregator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, trade: &Trade) {
+        if trade.token_amount == 0 {
+            return;
+        }
+        for interval in Interval::ALL {
+            let start = interval.bucket_start(trade.timestamp);
+            self.series
+                .entry((trade.mint, interval))
+                .or_default()
+                .entry(start)
+                .and_modify(|bucket| bucket.add(trade))
+                .or_insert_with(|| Bucket::new(trade));
+        }
+    }
+
+    /// Candles for the buckets covering `from..=to`.
+    ///
+    /// Buckets without trades are filled with a flat, zero-volume candle at the previous close.
+    /// Buckets before the first trade of the mint are omitted, since there is no price yet.
+    pub fn candles(&self, mint: &Pubkey, interval: Interval, from: i64, to: i64) -> Vec<Candle> {
+        let Some(series) = self.series.get(&(*mint, interval)) else {
+            return Vec::new();
+        };
+        let first = interval.bucket_start(from);
+        let last = interval.bucket_start(to);
+
+        let mut previous_close = series
+            .range(..first)
+            .next_back()
+            .map(|(_, bucket)| bucket.close);
+        let mut candles = Vec::new();
+        let mut start = first;
+        while start <= last {
+            if let Some(bucket) = series.get(&start) {
+                candles.push(bucket.candle(start));
+                previous_close = Some(bucket.close);
+            } else if let Some(close) = previous_close {
+                candles.push(Candle {
+                    start,
+                    open: close,
+                    high: close,
+                    low: close,
+                    close,
+                    volume_sol: 0,
+                    volume_tokens: 0,
+                    trades: 0,
+                });
+            }
+            start += interval.seconds();
+        }
+        candles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINT: Pubkey = Pubkey::new_from_array([1; 32]);
+
+    fn trade(timestamp: i64, index: u64, token_amount: u64, sol_amount: u64) -> Trade {
+        Trade {
+            mint: MINT,
+            timestamp,
+            sequence: (timestamp as u64, index),
+            token_amount,
+            sol_amount,
+        }
+    }
+
+    fn aggregate(trades: &[Trade]) -> CandleAggregator {
+        let mut aggregator = CandleAggregator::new();
+        for trade in trades {
+            aggregator.insert(trade);
+        }
+        aggregator
+    }
+
+    #[test]
+    fn buckets_trades_by_interval() {
+        let aggregator = aggregate(&[
+            trade(60, 0, 10, 20),
+            trade(75, 0, 10, 50),
+            trade(90, 0, 10, 10),
+            trade(119, 0, 10, 30),
+            trade(120, 0, 10, 40),
+        ]);
+
+        let candles = aggregator.candles(&MINT, Interval::OneMinute, 60, 120);
+        assert_eq!(
+            candles,
+            [
+                Candle {
+                    start: 60,
+                    open: 2.0,
+                    high: 5.0,
+                    low: 1.0,
+                    close: 3.0,
+                    volume_sol: 110,
+                    volume_tokens: 40,
+                    trades: 4,
+                },
+                Candle {
+                    start: 120,
+                    open: 4.0,
+                    high: 4.0,
+                    low: 4.0,
+                    close: 4.0,
+                    volume_sol: 40,
+                    volume_tokens: 10,
+                    trades: 1,
+                },
+            ]
+        );
+
+        let candles = aggregator.candles(&MINT, Interval::FiveMinutes, 0, 299);
+        assert_eq!(candles.len(), 1);
+        assert_eq!((candles[0].open, candles[0].close), (2.0, 4.0));
+        assert_eq!(candles[0].trades, 5);
+    }
+
+    #[test]
+    fn fills_gaps_at_the_previous_close() {
+        let aggregator = aggregate(&[trade(60, 0, 10, 20), trade(250, 0, 10, 30)]);
+
+        let candles = aggregator.candles(&MINT, Interval::OneMinute, 0, 300);
+        let summary: Vec<_> = candles
+            .iter()
+            .map(|candle| (candle.start, candle.open, candle.close, candle.trades))
+            .collect();
+        // Nothing before the first trade, flat candles between the trades and after the last
+        assert_eq!(
+            summary,
+            [
+                (60, 2.0, 2.0, 1),
+                (120, 2.0, 2.0, 0),
+                (180, 2.0, 2.0, 0),
+                (240, 3.0, 3.0, 1),
+                (300, 3.0, 3.0, 0),
+            ]
+        );
+        assert_eq!(candles[1].volume_sol, 0);
+
+        // A range starting after a trade picks up its close
+        let candles = aggregator.candles(&MINT, Interval::OneMinute, 180, 180);
+        assert_eq!(candles[0].open, 2.0);
+        assert!(aggregator
+            .candles(&Pubkey::default(), Interval::OneMinute, 0, 300)
+            .is_empty());
+    }
+
+    #[test]
+    fn late_trades_aggregate_like_a_replay_in_order() {
+        let trades = [
+            trade(61, 0, 10, 20),
+            trade(62, 0, 10, 60),
+            trade(62, 1, 10, 5),
+            trade(100, 0, 10, 30),
+            trade(130, 0, 10, 40),
+        ];
+        let in_order = aggregate(&trades);
+
+        // The earliest, a same-second trade and the close of its bucket arrive last
+        let mut late = CandleAggregator::new();
+        for index in [1, 4, 3, 2, 0] {
+            late.insert(&trades[index]);
+        }
+
+        for interval in Interval::ALL {
+            assert_eq!(
+                late.candles(&MINT, interval, 0, 200),
+                in_order.candles(&MINT, interval, 0, 200)
+            );
+        }
+        let candle = &late.candles(&MINT, Interval::OneMinute, 60, 60)[0];
+        assert_eq!((candle.open, candle.close), (2.0, 3.0));
+        assert_eq!((candle.high, candle.low), (6.0, 0.5));
+    }
+
+    #[test]
+    fn skips_trades_without_tokens() {
+        let aggregator = aggregate(&[trade(60, 0, 0, 20)]);
+        assert!(aggregator
+            .candles(&MINT, Interval::OneMinute, 0, 120)
+            .is_empty());
+    }
+
+    #[test]
+    fn parses_intervals() {
+        for interval in Interval::ALL {
+            assert_eq!(interval.to_string().parse::<Interval>(), Ok(interval));
+        }
+        assert!("2m".parse::<Interval>().is_err());
+        assert_eq!(Interval::OneHour.bucket_start(7_199), 3_600);
+        assert_eq!(Interval::OneMinute.bucket_start(-1), -60);
+    }
+}
@@ -0,0 +1,330 @@
+use anchor_lang::{Discriminator, Event};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use fame::events::{
+    FeeCollected, FeeWithdrawn, IntentExecuted, LaunchFeeCollected, LiquidityAdded,
+    LiquidityRemoved, OrderCancelled, OrderExecuted, OrderPlaced, PoolVerified, PriceUpdate,
+    TokenBurned, TokenCreated, TokenMinted, VestedClaimed,
+};
+
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// An event emitted by the `fame` program.
+pub enum FameEvent {
+    TokenCreated(TokenCreated),
+    TokenMinted(TokenMinted),
+    TokenBurned(TokenBurned),
+    LiquidityAdded(LiquidityAdded),
+    LiquidityRemoved(LiquidityRemoved),
+    FeeCollected(FeeCollected),
+    PriceUpdate(PriceUpdate),
+    FeeWithdrawn(FeeWithdrawn),
+    PoolVerified(PoolVerified),
+    VestedClaimed(VestedClaimed),
+    LaunchFeeCollected(LaunchFeeCollected),
+    IntentExecuted(IntentExecuted),
+    OrderPlaced(OrderPlaced),
+    OrderExecuted(OrderExecuted),
+    OrderCancelled(OrderCancelled),
+}
+
+impl FameEvent {
+    /// Decodes the payload of a `Program data:` log line (discriminator followed by the
+    /// borsh-encoded event). Returns `None` for anything that is not a known `fame` event.
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() < 8 {
+            return None;
+        }
+        let (discriminator, body) = data.split_at(8);
+        let discriminator: [u8; 8] = discriminator.try_into().ok()?;
+
+        match discriminator {
+            TokenCreated::DISCRIMINATOR => decode_body(body).map(Self::TokenCreated),
+            TokenMinted::DISCRIMINATOR => decode_body(body).map(Self::TokenMinted),
+            TokenBurned::DISCRIMINATOR => decode_body(body).map(Self::TokenBurned),
+            LiquidityAdded::DISCRIMINATOR => decode_body(body).map(Self::LiquidityAdded),
+            LiquidityRemoved::DISCRIMINATOR => decode_body(body).map(Self::LiquidityRemoved),
+            FeeCollected::DISCRIMINATOR => decode_body(body).map(Self::FeeCollected),
+            PriceUpdate::DISCRIMINATOR => decode_body(body).map(Self::PriceUpdate),
+            FeeWithdrawn::DISCRIMINATOR => decode_body(body).map(Self::FeeWithdrawn),
+            PoolVerified::DISCRIMINATOR => decode_body(body).map(Self::PoolVerified),
+            VestedClaimed::DISCRIMINATOR => decode_body(body).map(Self::VestedClaimed),
+            LaunchFeeCollected::DISCRIMINATOR => decode_body(body).map(Self::LaunchFeeCollected),
+            IntentExecuted::DISCRIMINATOR => decode_body(body).map(Self::IntentExecuted),
+            OrderPlaced::DISCRIMINATOR => decode_body(body).map(Self::OrderPlaced),
+            OrderExecuted::DISCRIMINATOR => decode_body(body).map(Self::OrderExecuted),
+            OrderCancelled::DISCRIMINATOR => decode_body(body).map(Self::OrderCancelled),
+            _ => None,
+        }
+    }
+}
+
+fn decode_body<E: Event>(mut body: &[u8]) -> Option<E> {
+    E::deserialize(&mut body).ok()
+}
+
+/// Extracts the `fame` events from the log messages of a single transaction, in emission order.
+///
+/// Only `Program data:` lines logged while the `fame` program is at the top of the invocation
+/// stack are decoded, so events from other programs with colliding discriminators are ignored.
+pub fn events_from_logs(logs: &[String]) -> Vec<FameEvent> {
+    let program_id = fame::ID.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for log in logs {
+        if let Some(data) = log.strip_prefix(PROGRAM_DATA_PREFIX) {
+            if stack.last() != Some(&program_id.as_str()) {
+                continue;
+            }
+            let Ok(bytes) = STANDARD.decode(data.trim()) else {
+                continue;
+            };
+            if let Some(event) = FameEvent::decode(&bytes) {
+                events.push(event);
+            }
+            continue;
+        }
+
+        let Some(rest) = log.strip_prefix("Program ") else {
+            continue;
+        };
+        let mut parts = rest.split_whitespace();
+        match (parts.next(), parts.next()) {
+            (Some(id), Some("invoke")) => stack.push(id),
+            (Some(_), Some("success")) | (Some(_), Some("failed:")) => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::fixture;
+
+    fn kinds(events: &[FameEvent]) -> Vec<&'static str> {
+        events
+            .iter()
+            .map(|event| match event {
+                FameEvent::TokenCreated(_) => "TokenCreated",
+                FameEvent::TokenMinted(_) => "TokenMinted",
+                FameEvent::TokenBurned(_) => "TokenBurned",
+                FameEvent::FeeCollected(_) => "FeeCollected",
+                FameEvent::PriceUpdate(_) => "PriceUpdate",
+                FameEvent::FeeWithdrawn(_) => "FeeWithdrawn",
+                FameEvent::PoolVerified(_) => "PoolVerified",
+                FameEvent::VestedClaimed(_) => "VestedClaimed",
+                FameEvent::LaunchFeeCollected(_) => "LaunchFeeCollected",
+                FameEvent::IntentExecuted(_) => "IntentExecuted",
+                FameEvent::OrderPlaced(_) => "OrderPlaced",
+                FameEvent::OrderExecuted(_) => "OrderExecuted",
+                FameEvent::OrderCancelled(_) => "OrderCancelled",
+                _ => "other",
+            })
+            .collect()
+    }
+
+    #[test]
+    fn decodes_recorded_trades() {
+        let transactions = fixture("trades");
+        let events: Vec<_> = transactions
+            .iter()
+            .map(|transaction| kinds(&events_from_logs(&transaction.logs)))
+            .collect();
+        assert_eq!(
+            events,
+            [
+                vec!["TokenCreated"],
+                vec!["FeeCollected", "TokenMinted", "PriceUpdate"],
+                vec!["FeeCollected", "TokenMinted", "PriceUpdate"],
+                vec!["FeeCollected", "TokenBurned", "PriceUpdate"],
+                vec!["FeeCollected", "TokenBurned", "PriceUpdate"],
+                vec!["FeeWithdrawn"],
+            ]
+        );
+
+        let created = events_from_logs(&transactions[0].logs);
+        let minted = events_from_logs(&transactions[1].logs);
+        let (FameEvent::TokenCreated(created), FameEvent::TokenMinted(minted)) =
+            (&created[0], &minted[1])
+        else {
+            panic!("expected a creation and a buy");
+        };
+        assert_eq!(minted.token, created.token);
+        assert_eq!(minted.price, 1_000_000_000);
+        assert!(minted.amount > 0);
+    }
+
+    #[test]
+    fn decodes_recorded_pool_verification() {
+        let transactions = fixture("verify_pool");
+        let events = events_from_logs(&transactions[2].logs);
+        assert_eq!(kinds(&events), ["PoolVerified"]);
+        let FameEvent::PoolVerified(report) = &events[0] else {
+            unreachable!();
+        };
+        assert!(report.solvent);
+        assert!(report.total_supply > 0);
+        assert!(report.balance >= report.required_reserves);
+    }
+
+    #[test]
+    fn decodes_recorded_vesting_claims() {
+        let transactions = fixture("vesting");
+        let events: Vec<_> = transactions
+            .iter()
+            .map(|transaction| events_from_logs(&transaction.logs))
+            .collect();
+        assert_eq!(kinds(&events[1]), ["VestedClaimed"]);
+        assert_eq!(kinds(&events[2]), ["VestedClaimed"]);
+
+        let Some(FameEvent::TokenCreated(created)) = events[0].last() else {
+            panic!("expected the creation last");
+        };
+        let claimed: u64 = events[1..]
+            .iter()
+            .map(|events| match &events[0] {
+                FameEvent::VestedClaimed(claim) => {
+                    assert_eq!(claim.token, created.token);
+                    assert_eq!(claim.beneficiary, created.creator);
+                    claim.amount
+                }
+                _ => unreachable!(),
+            })
+            .sum();
+        assert!(created.creator_allocation > 0);
+        assert_eq!(claimed, created.creator_allocation);
+    }
+
+    #[test]
+    fn decodes_recorded_launch_fees() {
+        let transactions = fixture("launch_fee");
+        let events = events_from_logs(&transactions[1].logs);
+        assert_eq!(
+            kinds(&events),
+            [
+                "FeeCollected",
+                "LaunchFeeCollected",
+                "TokenMinted",
+                "PriceUpdate"
+            ]
+        );
+        let (FameEvent::LaunchFeeCollected(launch_fee), FameEvent::TokenMinted(minted)) =
+            (&events[1], &events[2])
+        else {
+            unreachable!();
+        };
+        assert_eq!(launch_fee.user, minted.payer);
+        assert!(launch_fee.amount > 0);
+        assert!(launch_fee.burned);
+
+        // Sells pay the protocol fee only
+        let events = events_from_logs(&transactions[2].logs);
+        assert_eq!(
+            kinds(&events),
+            ["FeeCollected", "TokenBurned", "PriceUpdate"]
+        );
+    }
+
+    #[test]
+    fn decodes_recorded_intents() {
+        let transactions = fixture("intent");
+        let events = events_from_logs(&transactions[1].logs);
+        assert_eq!(
+            kinds(&events),
+            [
+                "FeeCollected",
+                "TokenMinted",
+                "PriceUpdate",
+                "IntentExecuted"
+            ]
+        );
+        let (FameEvent::TokenMinted(minted), FameEvent::IntentExecuted(intent)) =
+            (&events[1], &events[3])
+        else {
+            unreachable!();
+        };
+        assert_eq!(intent.token, minted.token);
+        assert_eq!(intent.user, minted.user);
+        assert_ne!(intent.relayer, intent.user);
+        assert_eq!(intent.nonce, 7);
+        assert_eq!(intent.amount_out, minted.amount);
+    }
+
+    #[test]
+    fn decodes_recorded_orders() {
+        let transactions = fixture("orders");
+        let events: Vec<_> = transactions[2..]
+            .iter()
+            .map(|transaction| events_from_logs(&transaction.logs))
+            .collect();
+        assert_eq!(kinds(&events[0]), ["OrderPlaced", "OrderPlaced"]);
+        assert_eq!(
+            kinds(&events[1]),
+            [
+                "FeeCollected",
+                "TokenMinted",
+                "PriceUpdate",
+                "OrderExecuted"
+            ]
+        );
+        assert_eq!(kinds(&events[2]), ["OrderCancelled"]);
+
+        let (
+            FameEvent::OrderPlaced(buy),
+            FameEvent::OrderPlaced(sell),
+            FameEvent::TokenMinted(minted),
+            FameEvent::OrderExecuted(executed),
+            FameEvent::OrderCancelled(cancelled),
+        ) = (
+            &events[0][0],
+            &events[0][1],
+            &events[1][1],
+            &events[1][3],
+            &events[2][0],
+        )
+        else {
+            unreachable!();
+        };
+        assert!(buy.buy && !sell.buy);
+        assert_eq!(buy.amount, 1_000_000_000);
+        assert_eq!(executed.order, buy.order);
+        assert_eq!(executed.owner, buy.owner);
+        assert_eq!(minted.user, buy.owner);
+        assert_eq!(minted.payer, executed.keeper);
+        assert_eq!(executed.amount_out, minted.amount);
+        assert_eq!(
+            executed.keeper_reward + minted.price,
+            buy.amount,
+            "the keeper is paid out of the escrow"
+        );
+        assert_eq!(cancelled.order, sell.order);
+    }
+
+    #[test]
+    fn ignores_data_logged_by_other_programs() {
+        let logs = &fixture("trades")[1].logs;
+        let data = logs
+            .iter()
+            .find(|log| log.starts_with(PROGRAM_DATA_PREFIX))
+            .unwrap();
+        let other = "Program 11111111111111111111111111111111";
+        let logs = [
+            format!("{other} invoke [1]"),
+            data.clone(),
+            format!("{other} success"),
+        ];
+        assert!(events_from_logs(&logs).is_empty());
+    }
+
+    #[test]
+    fn rejects_unknown_and_truncated_data() {
+        assert!(FameEvent::decode(&[0; 7]).is_none());
+        assert!(FameEvent::decode(&[0; 64]).is_none());
+        assert!(FameEvent::decode(&TokenMinted::DISCRIMINATOR).is_none());
+    }
+}
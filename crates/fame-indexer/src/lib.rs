@@ -0,0 +1,6 @@
+//! Indexing of `fame` program events into SQLite.
+
+pub mod candles;
+pub mod decode;
+pub mod source;
+pub mod store;
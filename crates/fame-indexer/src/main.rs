@@ -0,0 +1,133 @@
+use std::path::PathBuf;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use fame_indexer::candles::{CandleAggregator, Interval};
+use fame_indexer::decode::events_from_logs;
+use fame_indexer::source::{self, TransactionLogs};
+use fame_indexer::store::Store;
+
+#[derive(Parser)]
+#[command(name = "fame-indexer", about = "Index fame program events into SQLite")]
+struct Cli {
+    /// SQLite database to write to, created if missing
+    #[arg(long, default_value = "fame.sqlite")]
+    db: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Index `getTransaction` JSON responses saved to files
+    Files { paths: Vec<PathBuf> },
+    /// Index transactions fetched from an RPC node
+    Rpc {
+        #[arg(long, default_value = "http://127.0.0.1:8899")]
+        url: String,
+
+        /// Maximum number of new transactions to index, oldest first; the rest are left to the
+        /// next run
+        #[arg(long, default_value_t = 10_000)]
+        limit: usize,
+    },
+    /// Print OHLCV candles of an indexed mint as CSV
+    Candles {
+        #[arg(long)]
+        mint: Pubkey,
+
+        /// One of 1m, 5m, 1h, 1d
+        #[arg(long, default_value_t = Interval::OneMinute)]
+        interval: Interval,
+
+        /// First unix timestamp to cover, defaults to the first trade
+        #[arg(long)]
+        from: Option<i64>,
+
+        /// Last unix timestamp to cover, defaults to the last trade
+        #[arg(long)]
+        to: Option<i64>,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut store = Store::open(&cli.db)?;
+
+    let transactions = match cli.command {
+        Command::Files { paths } => {
+            let mut transactions = Vec::new();
+            for path in &paths {
+                transactions.extend(source::read_file(path)?);
+            }
+            transactions
+        }
+        Command::Rpc { url, limit } => {
+            source::fetch_from_rpc(&url, &fame::ID, limit, |signature| {
+                store.is_indexed(signature)
+            })?
+        }
+        Command::Candles {
+            mint,
+            interval,
+            from,
+            to,
+        } => return print_candles(&store, &mint, interval, from, to),
+    };
+
+    let (indexed, skipped) = index(&mut store, &transactions)?;
+    println!("indexed {indexed} transactions, skipped {skipped} already indexed");
+    Ok(())
+}
+
+fn index(store: &mut Store, transactions: &[TransactionLogs]) -> Result<(usize, usize)> {
+    let mut indexed = 0;
+    let mut skipped = 0;
+    for transaction in transactions {
+        let events = events_from_logs(&transaction.logs);
+        if store.apply(transaction, &events)? {
+            indexed += 1;
+        } else {
+            skipped += 1;
+        }
+    }
+    Ok((indexed, skipped))
+}
+
+fn print_candles(
+    store: &Store,
+    mint: &Pubkey,
+    interval: Interval,
+    from: Option<i64>,
+    to: Option<i64>,
+) -> Result<()> {
+    let trades = store.trades(mint)?;
+    let (Some(first), Some(last)) = (trades.first(), trades.last()) else {
+        return Ok(());
+    };
+    let from = from.unwrap_or(first.timestamp);
+    let to = to.unwrap_or(last.timestamp);
+
+    let mut aggregator = CandleAggregator::new();
+    for trade in &trades {
+        aggregator.insert(trade);
+    }
+
+    println!("start,open,high,low,close,volume_sol,volume_tokens,trades");
+    for candle in aggregator.candles(mint, interval, from, to) {
+        println!(
+            "{},{},{},{},{},{},{},{}",
+            candle.start,
+            candle.open,
+            candle.high,
+            candle.low,
+            candle.close,
+            candle.volume_sol,
+            candle.volume_tokens,
+            candle.trades
+        );
+    }
+    Ok(())
+}
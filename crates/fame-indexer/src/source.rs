@@ -0,0 +1,251 @@
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::UiTransactionEncoding;
+
+/// Maximum number of signatures returned by a single `getSignaturesForAddress` call.
+const SIGNATURES_PAGE_SIZE: usize = 1000;
+
+/// The parts of a confirmed transaction the indexer cares about.
+pub struct TransactionLogs {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub logs: Vec<String>,
+}
+
+impl TransactionLogs {
+    /// Parses a `getTransaction` result, either bare or wrapped in a JSON-RPC envelope.
+    ///
+    /// Returns `None` for failed transactions, since their state changes were rolled back.
+    pub fn from_json(value: &Value) -> Result<Option<Self>> {
+        let value = value.get("result").unwrap_or(value);
+
+        let meta = value
+            .get("meta")
+            .ok_or_else(|| anyhow!("transaction has no `meta`"))?;
+        if meta.get("err").is_some_and(|err| !err.is_null()) {
+            return Ok(None);
+        }
+
+        let signature = value
+            .pointer("/transaction/signatures/0")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("transaction has no signature"))?
+            .to_string();
+        let slot = value
+            .get("slot")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| anyhow!("transaction {signature} has no `slot`"))?;
+        let block_time = value.get("blockTime").and_then(Value::as_i64);
+        let logs = meta
+            .get("logMessages")
+            .and_then(Value::as_array)
+            .map(|logs| {
+                logs.iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Some(Self {
+            signature,
+            slot,
+            block_time,
+            logs,
+        }))
+    }
+}
+
+/// Reads transactions from a JSON file holding either a single `getTransaction` response or an
+/// array of them.
+pub fn read_file(path: &Path) -> Result<Vec<TransactionLogs>> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let value: Value = serde_json::from_str(&contents)
+        .with_context(|| format!("{} is not valid JSON", path.display()))?;
+
+    let values = match value {
+        Value::Array(values) => values,
+        value => vec![value],
+    };
+    let mut transactions = Vec::with_capacity(values.len());
+    for value in &values {
+        if let Some(transaction) = TransactionLogs::from_json(value)
+            .with_context(|| format!("invalid transaction in {}", path.display()))?
+        {
+            transactions.push(transaction);
+        }
+    }
+    Ok(transactions)
+}
+
+/// Fetches successful transactions that touched `program_id`, oldest first.
+///
+/// Only the oldest `limit` transactions not indexed yet are fetched, see [`unindexed_signatures`].
+pub fn fetch_from_rpc(
+    url: &str,
+    program_id: &Pubkey,
+    limit: usize,
+    is_indexed: impl FnMut(&str) -> Result<bool>,
+) -> Result<Vec<TransactionLogs>> {
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let signatures = unindexed_signatures(
+        limit,
+        |before| {
+            let before = before.map(Signature::from_str).transpose()?;
+            Ok(client.get_signatures_for_address_with_config(
+                program_id,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until: None,
+                    limit: Some(SIGNATURES_PAGE_SIZE),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                },
+            )?)
+        },
+        is_indexed,
+    )?;
+
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Json),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+    let mut transactions = Vec::with_capacity(signatures.len());
+    for signature in &signatures {
+        let transaction =
+            client.get_transaction_with_config(&Signature::from_str(signature)?, config)?;
+        if let Some(transaction) = TransactionLogs::from_json(&serde_json::to_value(transaction)?)?
+        {
+            transactions.push(transaction);
+        }
+    }
+    Ok(transactions)
+}
+
+/// Returns the oldest `limit` successful signatures newer than any indexed one, oldest first.
+///
+/// `page` returns the signatures before the given one, newest first. The walk goes back to the
+/// first signature for which `is_indexed` returns true, or to the start of the history. Events
+/// must be indexed in order, so a run that hits `limit` indexes the oldest part and the next
+/// run continues after the newest signature indexed so far.
+fn unindexed_signatures(
+    limit: usize,
+    mut page: impl FnMut(Option<&str>) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>>,
+    mut is_indexed: impl FnMut(&str) -> Result<bool>,
+) -> Result<Vec<String>> {
+    let mut signatures = Vec::new();
+    let mut before = None;
+    'pages: loop {
+        let statuses = page(before.as_deref())?;
+        let Some(last) = statuses.last() else {
+            break;
+        };
+        before = Some(last.signature.clone());
+
+        for status in statuses {
+            if is_indexed(&status.signature)? {
+                break 'pages;
+            }
+            if status.err.is_none() {
+                signatures.push(status.signature);
+            }
+        }
+    }
+
+    signatures.reverse();
+    signatures.truncate(limit);
+    Ok(signatures)
+}
+
+/// Transactions recorded from the program tests, in `tests/fixtures/<name>.json`.
+#[cfg(test)]
+pub(crate) fn fixture(name: &str) -> Vec<TransactionLogs> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(format!("{name}.json"));
+    read_file(&path).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use solana_sdk::transaction::TransactionError;
+
+    use super::*;
+
+    /// A history of `len` signatures, every third one failed.
+    fn history(len: usize) -> Vec<RpcConfirmedTransactionStatusWithSignature> {
+        (0..len)
+            .map(|i| RpcConfirmedTransactionStatusWithSignature {
+                signature: format!("sig{i}"),
+                slot: i as u64,
+                err: (i % 3 == 2).then_some(TransactionError::AccountNotFound),
+                memo: None,
+                block_time: None,
+                confirmation_status: None,
+            })
+            .collect()
+    }
+
+    /// Walks `history` in pages of two, newest first.
+    fn walk(
+        history: &[RpcConfirmedTransactionStatusWithSignature],
+        limit: usize,
+        indexed: &HashSet<String>,
+    ) -> Vec<String> {
+        unindexed_signatures(
+            limit,
+            |before| {
+                let end = before.map_or(history.len(), |before| {
+                    history.iter().position(|s| s.signature == before).unwrap()
+                });
+                Ok(history[end.saturating_sub(2)..end]
+                    .iter()
+                    .rev()
+                    .cloned()
+                    .collect())
+            },
+            |signature| Ok(indexed.contains(signature)),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn truncated_runs_continue_where_they_stopped() {
+        let mut history = history(10);
+        let successful: Vec<String> = history
+            .iter()
+            .filter(|status| status.err.is_none())
+            .map(|status| status.signature.clone())
+            .collect();
+
+        let mut indexed = HashSet::new();
+        let mut order = Vec::new();
+        for _ in 0..3 {
+            let signatures = walk(&history, 3, &indexed);
+            assert!(signatures.len() <= 3);
+            indexed.extend(signatures.iter().cloned());
+            order.extend(signatures);
+        }
+        assert_eq!(order, successful);
+        assert!(walk(&history, 3, &indexed).is_empty());
+
+        // New transactions land after the indexed ones
+        history.extend(self::history(13).split_off(10));
+        assert_eq!(walk(&history, 3, &indexed), ["sig10", "sig12"]);
+    }
+}
@@ -0,0 +1,761 @@
+use std::path::Path;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+
+use crate::candles::Trade;
+use crate::decode::FameEvent;
+use crate::source::TransactionLogs;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS transactions (
+    signature   TEXT PRIMARY KEY,
+    slot        INTEGER NOT NULL,
+    block_time  INTEGER
+);
+
+CREATE TABLE IF NOT EXISTS tokens (
+    mint                TEXT PRIMARY KEY,
+    name                TEXT NOT NULL,
+    symbol              TEXT NOT NULL,
+    social_account_url  TEXT NOT NULL,
+    creator             TEXT NOT NULL,
+    total_supply        INTEGER NOT NULL DEFAULT 0,
+    signature           TEXT NOT NULL REFERENCES transactions (signature),
+    slot                INTEGER NOT NULL,
+    block_time          INTEGER
+);
+
+CREATE TABLE IF NOT EXISTS trades (
+    signature     TEXT NOT NULL REFERENCES transactions (signature),
+    event_index   INTEGER NOT NULL,
+    slot          INTEGER NOT NULL,
+    block_time    INTEGER,
+    mint          TEXT NOT NULL,
+    user          TEXT NOT NULL,
+    side          TEXT NOT NULL CHECK (side IN ('buy', 'sell')),
+    token_amount  INTEGER NOT NULL,
+    sol_amount    INTEGER NOT NULL,
+    fee           INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS trades_by_mint ON trades (mint, slot);
+
+CREATE TABLE IF NOT EXISTS fee_withdrawals (
+    signature    TEXT NOT NULL REFERENCES transactions (signature),
+    event_index  INTEGER NOT NULL,
+    slot         INTEGER NOT NULL,
+    block_time   INTEGER,
+    receiver     TEXT NOT NULL,
+    amount       INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE TABLE IF NOT EXISTS pool_verifications (
+    signature          TEXT NOT NULL REFERENCES transactions (signature),
+    event_index        INTEGER NOT NULL,
+    slot               INTEGER NOT NULL,
+    block_time         INTEGER,
+    mint               TEXT NOT NULL,
+    total_supply       INTEGER NOT NULL,
+    required_reserves  INTEGER NOT NULL,
+    balance            INTEGER NOT NULL,
+    accumulated_fees   INTEGER NOT NULL,
+    lamports           INTEGER NOT NULL,
+    solvent            INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE TABLE IF NOT EXISTS launch_fees (
+    signature    TEXT NOT NULL REFERENCES transactions (signature),
+    event_index  INTEGER NOT NULL,
+    slot         INTEGER NOT NULL,
+    block_time   INTEGER,
+    mint         TEXT NOT NULL,
+    user         TEXT NOT NULL,
+    amount       INTEGER NOT NULL,
+    burned       INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE TABLE IF NOT EXISTS intents (
+    signature    TEXT NOT NULL REFERENCES transactions (signature),
+    event_index  INTEGER NOT NULL,
+    slot         INTEGER NOT NULL,
+    block_time   INTEGER,
+    mint         TEXT NOT NULL,
+    user         TEXT NOT NULL,
+    relayer      TEXT NOT NULL,
+    nonce        INTEGER NOT NULL,
+    amount_out   INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE TABLE IF NOT EXISTS orders (
+    signature      TEXT NOT NULL REFERENCES transactions (signature),
+    event_index    INTEGER NOT NULL,
+    slot           INTEGER NOT NULL,
+    block_time     INTEGER,
+    address        TEXT NOT NULL,
+    mint           TEXT NOT NULL,
+    owner          TEXT NOT NULL,
+    side           TEXT NOT NULL CHECK (side IN ('buy', 'sell')),
+    amount         INTEGER NOT NULL,
+    trigger_price  INTEGER NOT NULL,
+    status         TEXT NOT NULL DEFAULT 'open'
+                   CHECK (status IN ('open', 'executed', 'cancelled')),
+    closed_by      TEXT REFERENCES transactions (signature),
+    keeper         TEXT,
+    amount_out     INTEGER,
+    keeper_reward  INTEGER,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS open_orders_by_address ON orders (address, status);
+
+CREATE TABLE IF NOT EXISTS holders (
+    mint     TEXT NOT NULL,
+    owner    TEXT NOT NULL,
+    balance  INTEGER NOT NULL,
+    PRIMARY KEY (mint, owner)
+);
+";
+
+/// SQLite-backed storage for indexed `fame` state.
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)
+            .with_context(|| format!("failed to open database {}", path.display()))?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// Returns true if the transaction with this signature has already been indexed.
+    pub fn is_indexed(&self, signature: &str) -> Result<bool> {
+        let found = self
+            .conn
+            .query_row(
+                "SELECT 1 FROM transactions WHERE signature = ?1",
+                [signature],
+                |_| Ok(()),
+            )
+            .optional()?;
+        Ok(found.is_some())
+    }
+
+    /// Persists the events of one transaction atomically.
+    ///
+    /// Replaying an already indexed signature is a no-op, so sources can be re-read freely.
+    /// Returns whether the transaction was newly indexed.
+    pub fn apply(&mut self, transaction: &TransactionLogs, events: &[FameEvent]) -> Result<bool> {
+        let tx = self.conn.transaction()?;
+        let inserted = tx.execute(
+            "INSERT OR IGNORE INTO transactions (signature, slot, block_time) VALUES (?1, ?2, ?3)",
+            params![
+                transaction.signature,
+                to_sql_int(transaction.slot)?,
+                transaction.block_time
+            ],
+        )?;
+        if inserted == 0 {
+            return Ok(false);
+        }
+
+        let mut fees = 0;
+        for (index, event) in events.iter().enumerate() {
+            apply_event(&tx, transaction, index, event, &mut fees)?;
+        }
+        tx.commit()?;
+        Ok(true)
+    }
+
+    /// All indexed trades of `mint` that have a block time, in chronological order, priced
+    /// without their fees.
+    pub fn trades(&self, mint: &Pubkey) -> Result<Vec<Trade>> {
+        let mut statement = self.conn.prepare(
+            "SELECT block_time, slot, event_index, token_amount,
+                    CASE side WHEN 'buy' THEN sol_amount - fee ELSE sol_amount + fee END
+             FROM trades
+             WHERE mint = ?1 AND block_time IS NOT NULL
+             ORDER BY block_time, slot, event_index",
+        )?;
+        let rows = statement.query_map([mint.to_string()], |row| {
+            Ok(Trade {
+                mint: *mint,
+                timestamp: row.get(0)?,
+                sequence: (row.get(1)?, row.get(2)?),
+                token_amount: row.get(3)?,
+                sol_amount: row.get(4)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+}
+
+fn apply_event(
+    tx: &Transaction,
+    transaction: &TransactionLogs,
+    index: usize,
+    event: &FameEvent,
+    fees: &mut u64,
+) -> Result<()> {
+    let signature = &transaction.signature;
+    let slot = to_sql_int(transaction.slot)?;
+    let block_time = transaction.block_time;
+
+    match event {
+        // The creator's initial buy is emitted before the token, so its supply is counted here
+        // together with the allocation minted to the vesting account
+        FameEvent::TokenCreated(event) => {
+            let allocation = to_sql_int(event.creator_allocation)?;
+            tx.execute(
+                "INSERT OR IGNORE INTO tokens
+                    (mint, name, symbol, social_account_url, creator, total_supply, signature,
+                     slot, block_time)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    event.token.to_string(),
+                    event.name,
+                    event.symbol,
+                    event.social_account_url,
+                    event.creator.to_string(),
+                    to_sql_int(event.initial_buy_tokens)? + allocation,
+                    signature,
+                    slot,
+                    block_time
+                ],
+            )?;
+            if allocation > 0 {
+                let vesting = vesting_address(&event.token);
+                adjust_balance(tx, &event.token.to_string(), &vesting, allocation)?;
+            }
+        }
+        FameEvent::TokenMinted(event) => {
+            let mint = event.token.to_string();
+            let user = event.user.to_string();
+            let amount = to_sql_int(event.amount)?;
+            insert_trade(
+                tx,
+                transaction,
+                index,
+                &mint,
+                &user,
+                "buy",
+                amount,
+                event.price,
+                std::mem::take(fees),
+            )?;
+            adjust_supply(tx, &mint, &user, amount)?;
+        }
+        FameEvent::TokenBurned(event) => {
+            let mint = event.token.to_string();
+            let user = event.user.to_string();
+            let amount = to_sql_int(event.amount)?;
+            insert_trade(
+                tx,
+                transaction,
+                index,
+                &mint,
+                &user,
+                "sell",
+                amount,
+                event.refund,
+                std::mem::take(fees),
+            )?;
+            adjust_supply(tx, &mint, &user, -amount)?;
+        }
+        // The fees of a trade are emitted right before it
+        FameEvent::FeeCollected(event) => *fees += event.amount,
+        // So is the launch fee above the protocol rate, which buyers pay on top
+        FameEvent::LaunchFeeCollected(event) => {
+            *fees += event.amount;
+            tx.execute(
+                "INSERT INTO launch_fees
+                    (signature, event_index, slot, block_time, mint, user, amount, burned)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    signature,
+                    index,
+                    slot,
+                    block_time,
+                    event.token.to_string(),
+                    event.user.to_string(),
+                    to_sql_int(event.amount)?,
+                    event.burned
+                ],
+            )?;
+        }
+        FameEvent::FeeWithdrawn(event) => {
+            tx.execute(
+                "INSERT INTO fee_withdrawals
+                    (signature, event_index, slot, block_time, receiver, amount)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    signature,
+                    index,
+                    slot,
+                    block_time,
+                    event.receiver.to_string(),
+                    to_sql_int(event.amount)?
+                ],
+            )?;
+        }
+        FameEvent::PoolVerified(event) => {
+            tx.execute(
+                "INSERT INTO pool_verifications
+                    (signature, event_index, slot, block_time, mint, total_supply,
+                     required_reserves, balance, accumulated_fees, lamports, solvent)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    signature,
+                    index,
+                    slot,
+                    block_time,
+                    event.token.to_string(),
+                    to_sql_int(event.total_supply)?,
+                    to_sql_int(event.required_reserves)?,
+                    to_sql_int(event.balance)?,
+                    to_sql_int(event.accumulated_fees)?,
+                    to_sql_int(event.lamports)?,
+                    event.solvent
+                ],
+            )?;
+        }
+        // Claims move tokens out of the vesting account without changing the supply
+        FameEvent::VestedClaimed(event) => {
+            let mint = event.token.to_string();
+            let amount = to_sql_int(event.amount)?;
+            adjust_balance(tx, &mint, &vesting_address(&event.token), -amount)?;
+            adjust_balance(tx, &mint, &event.beneficiary.to_string(), amount)?;
+        }
+        // The trade itself comes as its own TokenMinted or TokenBurned
+        FameEvent::IntentExecuted(event) => {
+            tx.execute(
+                "INSERT INTO intents
+                    (signature, event_index, slot, block_time, mint, user, relayer, nonce,
+                     amount_out)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    signature,
+                    index,
+                    slot,
+                    block_time,
+                    event.token.to_string(),
+                    event.user.to_string(),
+                    event.relayer.to_string(),
+                    to_sql_int(event.nonce)?,
+                    to_sql_int(event.amount_out)?
+                ],
+            )?;
+        }
+        FameEvent::OrderPlaced(event) => {
+            tx.execute(
+                "INSERT INTO orders
+                    (signature, event_index, slot, block_time, address, mint, owner, side, amount,
+                     trigger_price)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    signature,
+                    index,
+                    slot,
+                    block_time,
+                    event.order.to_string(),
+                    event.token.to_string(),
+                    event.owner.to_string(),
+                    if event.buy { "buy" } else { "sell" },
+                    to_sql_int(event.amount)?,
+                    // No spot price comes near either bound, so saturating keeps what the
+                    // trigger means
+                    i64::try_from(event.trigger_price).unwrap_or(i64::MAX)
+                ],
+            )?;
+        }
+        // The fill itself comes as its own TokenMinted or TokenBurned. A closed order's address
+        // may be reused by a later order, so only the open one is closed
+        FameEvent::OrderExecuted(event) => {
+            tx.execute(
+                "UPDATE orders
+                 SET status = 'executed', closed_by = ?2, keeper = ?3, amount_out = ?4,
+                     keeper_reward = ?5
+                 WHERE address = ?1 AND status = 'open'",
+                params![
+                    event.order.to_string(),
+                    signature,
+                    event.keeper.to_string(),
+                    to_sql_int(event.amount_out)?,
+                    to_sql_int(event.keeper_reward)?
+                ],
+            )?;
+        }
+        FameEvent::OrderCancelled(event) => {
+            tx.execute(
+                "UPDATE orders SET status = 'cancelled', closed_by = ?2
+                 WHERE address = ?1 AND status = 'open'",
+                params![event.order.to_string(), signature],
+            )?;
+        }
+        FameEvent::LiquidityAdded(_)
+        | FameEvent::LiquidityRemoved(_)
+        | FameEvent::PriceUpdate(_) => {}
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn insert_trade(
+    tx: &Transaction,
+    transaction: &TransactionLogs,
+    index: usize,
+    mint: &str,
+    user: &str,
+    side: &str,
+    token_amount: i64,
+    sol_amount: u64,
+    fee: u64,
+) -> Result<()> {
+    tx.execute(
+        "INSERT INTO trades
+            (signature, event_index, slot, block_time, mint, user, side, token_amount, sol_amount,
+             fee)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        params![
+            transaction.signature,
+            index,
+            to_sql_int(transaction.slot)?,
+            transaction.block_time,
+            mint,
+            user,
+            side,
+            token_amount,
+            to_sql_int(sol_amount)?,
+            to_sql_int(fee)?
+        ],
+    )?;
+    Ok(())
+}
+
+/// Applies a signed token amount to the mint's supply and the holder's balance.
+fn adjust_supply(tx: &Transaction, mint: &str, owner: &str, delta: i64) -> Result<()> {
+    tx.execute(
+        "UPDATE tokens SET total_supply = total_supply + ?2 WHERE mint = ?1",
+        params![mint, delta],
+    )?;
+    adjust_balance(tx, mint, owner, delta)
+}
+
+/// Applies a signed token amount to the holder's balance.
+fn adjust_balance(tx: &Transaction, mint: &str, owner: &str, delta: i64) -> Result<()> {
+    tx.execute(
+        "INSERT INTO holders (mint, owner, balance) VALUES (?1, ?2, ?3)
+         ON CONFLICT (mint, owner) DO UPDATE SET balance = balance + excluded.balance",
+        params![mint, owner, delta],
+    )?;
+    Ok(())
+}
+
+/// The vesting account holding the creator allocation of `mint` until it is claimed.
+fn vesting_address(mint: &Pubkey) -> String {
+    Pubkey::find_program_address(&[b"vesting", mint.as_ref()], &fame::ID)
+        .0
+        .to_string()
+}
+
+/// SQLite integers are signed 64-bit.
+fn to_sql_int(value: u64) -> Result<i64> {
+    i64::try_from(value).with_context(|| format!("{value} does not fit in an SQLite integer"))
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+    use fame::state::{fee_for, GlobalState};
+
+    use super::*;
+    use crate::decode::events_from_logs;
+    use crate::source::fixture;
+
+    fn store_with(name: &str) -> Store {
+        let mut store = Store::open(Path::new(":memory:")).unwrap();
+        for transaction in fixture(name) {
+            let events = events_from_logs(&transaction.logs);
+            assert!(store.apply(&transaction, &events).unwrap());
+        }
+        store
+    }
+
+    fn query(store: &Store, sql: &str) -> i64 {
+        store.conn.query_row(sql, [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn indexes_recorded_trades() {
+        let store = store_with("trades");
+
+        // The supply is what the buys minted less what the sells burned
+        let mut supply = 0;
+        for transaction in fixture("trades") {
+            for event in events_from_logs(&transaction.logs) {
+                match event {
+                    FameEvent::TokenMinted(event) => supply += event.amount as i64,
+                    FameEvent::TokenBurned(event) => supply -= event.amount as i64,
+                    _ => {}
+                }
+            }
+        }
+        assert!(supply > 0);
+        assert_eq!(query(&store, "SELECT total_supply FROM tokens"), supply);
+        assert_eq!(query(&store, "SELECT SUM(balance) FROM holders"), supply);
+        assert_eq!(query(&store, "SELECT COUNT(*) FROM holders"), 2);
+
+        assert_eq!(
+            query(&store, "SELECT COUNT(*) FROM trades WHERE side = 'buy'"),
+            2
+        );
+        assert_eq!(
+            query(&store, "SELECT COUNT(*) FROM trades WHERE side = 'sell'"),
+            2
+        );
+        assert_eq!(
+            query(&store, "SELECT amount FROM fee_withdrawals"),
+            1_000_000
+        );
+    }
+
+    #[test]
+    fn counts_the_initial_buy_emitted_before_the_token() {
+        let store = store_with("initial_buy");
+        let (initial_buy, bought) = match (
+            &events_from_logs(&fixture("initial_buy")[0].logs)[..],
+            &events_from_logs(&fixture("initial_buy")[1].logs)[..],
+        ) {
+            (
+                [.., FameEvent::TokenMinted(initial), _, FameEvent::TokenCreated(created)],
+                [.., FameEvent::TokenMinted(bought), _],
+            ) => {
+                assert_eq!(initial.amount, created.initial_buy_tokens);
+                (initial.amount as i64, bought.amount as i64)
+            }
+            _ => panic!("expected an initial buy before the creation, then a buy"),
+        };
+        assert!(initial_buy > 0);
+        assert_eq!(
+            query(&store, "SELECT total_supply FROM tokens"),
+            initial_buy + bought
+        );
+        assert_eq!(
+            query(&store, "SELECT SUM(balance) FROM holders"),
+            initial_buy + bought
+        );
+        assert_eq!(
+            query(&store, "SELECT COUNT(*) FROM trades WHERE side = 'buy'"),
+            2
+        );
+    }
+
+    #[test]
+    fn tracks_the_creator_allocation_through_its_claims() {
+        let transactions = fixture("vesting");
+        let Some(FameEvent::TokenCreated(created)) = events_from_logs(&transactions[0].logs).pop()
+        else {
+            panic!("expected the creation last");
+        };
+        let allocation = created.creator_allocation as i64;
+        let initial_buy = created.initial_buy_tokens as i64;
+        assert!(allocation > 0 && initial_buy > 0);
+
+        let balance = |store: &Store, owner: &str| -> i64 {
+            store
+                .conn
+                .query_row(
+                    "SELECT balance FROM holders WHERE owner = ?1",
+                    [owner],
+                    |row| row.get(0),
+                )
+                .unwrap()
+        };
+        let vesting = vesting_address(&created.token);
+        let creator = created.creator.to_string();
+
+        let mut store = Store::open(Path::new(":memory:")).unwrap();
+        let apply = |store: &mut Store, transaction: &TransactionLogs| {
+            let events = events_from_logs(&transaction.logs);
+            store.apply(transaction, &events).unwrap();
+        };
+        apply(&mut store, &transactions[0]);
+        apply(&mut store, &transactions[1]);
+        let supply = query(&store, "SELECT total_supply FROM tokens");
+        assert_eq!(supply, allocation + initial_buy);
+        let claimed = balance(&store, &creator) - initial_buy;
+        assert!(claimed > 0 && claimed < allocation);
+        assert_eq!(balance(&store, &vesting), allocation - claimed);
+
+        apply(&mut store, &transactions[2]);
+        assert_eq!(query(&store, "SELECT total_supply FROM tokens"), supply);
+        assert_eq!(balance(&store, &vesting), 0);
+        assert_eq!(balance(&store, &creator), supply);
+        assert_eq!(query(&store, "SELECT SUM(balance) FROM holders"), supply);
+    }
+
+    #[test]
+    fn prices_protected_buys_without_the_launch_fee() {
+        let store = store_with("launch_fee");
+        let launch_fee = query(&store, "SELECT amount FROM launch_fees");
+        assert!(launch_fee > 0);
+        assert_eq!(query(&store, "SELECT burned FROM launch_fees"), 1);
+
+        // The buy paid the protocol fee and the launch fee on top of what the curve took
+        let protocol_fee = fee_for(LAMPORTS_PER_SOL, GlobalState::DEFAULT_FEE_BPS) as i64;
+        assert_eq!(
+            query(&store, "SELECT fee FROM trades WHERE side = 'buy'"),
+            protocol_fee + launch_fee
+        );
+
+        // Selling everything right back gets what the curve took, up to the price of the one
+        // base unit the buy rounded away
+        let mint: String = store
+            .conn
+            .query_row("SELECT mint FROM tokens", [], |row| row.get(0))
+            .unwrap();
+        let trades = store.trades(&mint.parse().unwrap()).unwrap();
+        let (bought, sold) = (trades[0].sol_amount, trades[1].sol_amount);
+        assert_eq!(
+            bought,
+            LAMPORTS_PER_SOL - protocol_fee as u64 - launch_fee as u64
+        );
+        assert!(sold <= bought && bought - sold <= trades[0].price() as u64);
+    }
+
+    #[test]
+    fn records_intents_next_to_their_trades() {
+        let store = store_with("intent");
+        assert_eq!(query(&store, "SELECT COUNT(*) FROM intents"), 1);
+        assert_eq!(query(&store, "SELECT nonce FROM intents"), 7);
+        assert_eq!(
+            query(
+                &store,
+                "SELECT COUNT(*) FROM intents
+                 JOIN trades USING (signature, mint, user)
+                 WHERE trades.side = 'buy' AND trades.token_amount = intents.amount_out"
+            ),
+            1
+        );
+        assert_eq!(
+            query(&store, "SELECT total_supply FROM tokens"),
+            query(&store, "SELECT amount_out FROM intents")
+        );
+    }
+
+    #[test]
+    fn tracks_orders_until_they_close() {
+        let transactions = fixture("orders");
+        let mut store = Store::open(Path::new(":memory:")).unwrap();
+        for transaction in &transactions[..3] {
+            let events = events_from_logs(&transaction.logs);
+            store.apply(transaction, &events).unwrap();
+        }
+        assert_eq!(
+            query(&store, "SELECT COUNT(*) FROM orders WHERE status = 'open'"),
+            2
+        );
+        // Both orders were placed at a trigger of u64::MAX
+        assert_eq!(
+            query(&store, "SELECT MIN(trigger_price) FROM orders"),
+            i64::MAX
+        );
+
+        for transaction in &transactions[3..] {
+            let events = events_from_logs(&transaction.logs);
+            store.apply(transaction, &events).unwrap();
+        }
+        let (status, closed_by, amount_out): (String, String, i64) = store
+            .conn
+            .query_row(
+                "SELECT status, closed_by, amount_out FROM orders WHERE side = 'buy'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(status, "executed");
+        assert_eq!(closed_by, transactions[3].signature);
+        assert_eq!(
+            amount_out,
+            query(
+                &store,
+                "SELECT token_amount FROM trades ORDER BY slot DESC LIMIT 1"
+            )
+        );
+        let status: String = store
+            .conn
+            .query_row("SELECT status FROM orders WHERE side = 'sell'", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(status, "cancelled");
+
+        // The owner held the escrowed tokens throughout, and bought more through the order
+        let bought = query(&store, "SELECT SUM(token_amount) FROM trades");
+        assert_eq!(query(&store, "SELECT balance FROM holders"), bought);
+        assert_eq!(query(&store, "SELECT total_supply FROM tokens"), bought);
+    }
+
+    #[test]
+    fn records_pool_verifications() {
+        let store = store_with("verify_pool");
+        assert_eq!(
+            query(&store, "SELECT total_supply FROM pool_verifications"),
+            query(&store, "SELECT total_supply FROM tokens")
+        );
+        assert_eq!(query(&store, "SELECT solvent FROM pool_verifications"), 1);
+    }
+
+    #[test]
+    fn replaying_a_transaction_is_a_no_op() {
+        let mut store = store_with("trades");
+        let supply = query(&store, "SELECT total_supply FROM tokens");
+
+        for transaction in fixture("trades") {
+            let events = events_from_logs(&transaction.logs);
+            assert!(!store.apply(&transaction, &events).unwrap());
+        }
+        assert_eq!(query(&store, "SELECT total_supply FROM tokens"), supply);
+        assert_eq!(query(&store, "SELECT COUNT(*) FROM trades"), 4);
+    }
+
+    #[test]
+    fn prices_buys_and_sells_without_fees() {
+        let store = store_with("trades");
+        let mint: String = store
+            .conn
+            .query_row("SELECT mint FROM tokens", [], |row| row.get(0))
+            .unwrap();
+        let trades = store.trades(&mint.parse().unwrap()).unwrap();
+        assert_eq!(trades.len(), 4);
+
+        // The second buyer sold everything right back, getting what the curve took for it up to
+        // the price of the one base unit the buy rounded away
+        assert_eq!(trades[1].token_amount, trades[2].token_amount);
+        let (bought, sold) = (trades[1].sol_amount, trades[2].sol_amount);
+        assert!(sold <= bought && bought - sold <= trades[1].price() as u64);
+
+        // The buyer paid the curve and the fee, the seller got the curve's payout less the fee
+        let row = |offset: i64| -> (i64, i64) {
+            store
+                .conn
+                .query_row(
+                    "SELECT sol_amount, fee FROM trades ORDER BY slot LIMIT 1 OFFSET ?1",
+                    [offset],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .unwrap()
+        };
+        let ((paid, buy_fee), (got, sell_fee)) = (row(1), row(2));
+        assert!(buy_fee > 0 && sell_fee > 0);
+        assert_eq!(paid, 500_000_000);
+        assert_eq!(paid - buy_fee, bought as i64);
+        assert_eq!(got + sell_fee, sold as i64);
+    }
+}
@@ -26,4 +26,60 @@ pub enum ErrorCode {
     ArithmeticUnderflow,
     #[msg("Invalid bonding curve parameters")]
     InvalidBondingCurveParameters,
+    #[msg("TWAP window must be positive")]
+    InvalidTwapWindow,
+    #[msg("Not enough price history for the requested TWAP window")]
+    InsufficientPriceHistory,
+    #[msg("Trading is paused")]
+    TradingPaused,
+    #[msg("Fee exceeds the maximum allowed")]
+    InvalidFee,
+    #[msg("Liquidity pool reserves do not cover the token supply")]
+    PoolInsolvent,
+    #[msg("Mint would exceed the max supply of the curve")]
+    MaxSupplyExceeded,
+    #[msg("Invalid creator allocation")]
+    InvalidAllocation,
+    #[msg("Invalid vesting schedule")]
+    InvalidVestingSchedule,
+    #[msg("No vested tokens to claim")]
+    NothingToClaim,
+    #[msg("Token has not launched yet")]
+    NotLaunched,
+    #[msg("Launch protection exceeds the protocol caps")]
+    InvalidLaunchProtection,
+    #[msg("Invalid holding limit")]
+    InvalidHoldingLimit,
+    #[msg("Buy exceeds the per-wallet holding limit")]
+    HoldingLimitExceeded,
+    #[msg("Buy exceeds the SOL limit per transaction")]
+    BuyLimitExceeded,
+    #[msg("Sell exceeds the token limit per transaction")]
+    SellLimitExceeded,
+    #[msg("Trade exceeds the token's volume limit for this slot")]
+    SlotVolumeExceeded,
+    #[msg("Invalid presale")]
+    InvalidPresale,
+    #[msg("Wallet is not on the presale allowlist")]
+    NotAllowlisted,
+    #[msg("Buy exceeds the per-wallet presale cap")]
+    PresaleCapExceeded,
+    #[msg("Invalid batch of trades")]
+    InvalidBatch,
+    #[msg("Trade fell short of its limit")]
+    SlippageExceeded,
+    #[msg("Invalid wrapped SOL account")]
+    InvalidWsolAccount,
+    #[msg("Trade intent has expired")]
+    IntentExpired,
+    #[msg("Trade intent lacks the user's signature")]
+    InvalidIntentSignature,
+    #[msg("Intent nonce already used")]
+    NonceUsed,
+    #[msg("Invalid order")]
+    InvalidOrder,
+    #[msg("Price has not reached the order's trigger")]
+    OrderNotTriggered,
+    #[msg("Portfolio still holds tokens")]
+    PortfolioNotEmpty,
 }
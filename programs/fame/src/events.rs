@@ -17,14 +17,22 @@ pub struct TokenCreated {
     pub symbol: String,
     pub social_account_url: String,
     pub creator: Pubkey,
+    // Lamports the creator spent on the first buy and the tokens it minted, zero without one
+    pub initial_buy_sol: u64,
+    pub initial_buy_tokens: u64,
+    // Tokens locked in the creator's vesting account, zero without an allocation
+    pub creator_allocation: u64,
+    pub launch_at: i64,
 }
 
 #[event]
 pub struct TokenMinted {
     pub token: Pubkey,
+    // Wallet credited with the tokens, and the one that paid for them
     pub user: Pubkey,
     pub amount: u64,
     pub price: u64,
+    pub payer: Pubkey,
 }
 
 #[event]
@@ -64,3 +72,67 @@ pub struct FeeWithdrawn {
     pub amount: u64,
     pub receiver: Pubkey,
 }
+
+#[event]
+pub struct PoolVerified {
+    pub token: Pubkey,
+    pub total_supply: u64,
+    pub required_reserves: u64,
+    pub balance: u64,
+    pub accumulated_fees: u64,
+    pub lamports: u64,
+    pub solvent: bool,
+}
+
+#[event]
+pub struct VestedClaimed {
+    pub token: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct LaunchFeeCollected {
+    pub token: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub burned: bool,
+}
+
+#[event]
+pub struct IntentExecuted {
+    pub token: Pubkey,
+    pub user: Pubkey,
+    pub relayer: Pubkey,
+    pub nonce: u64,
+    // Tokens minted by a buy, or lamports paid out by a sell
+    pub amount_out: u64,
+}
+
+#[event]
+pub struct OrderPlaced {
+    pub order: Pubkey,
+    pub token: Pubkey,
+    pub owner: Pubkey,
+    pub buy: bool,
+    pub amount: u64,
+    pub trigger_price: u64,
+}
+
+#[event]
+pub struct OrderExecuted {
+    pub order: Pubkey,
+    pub token: Pubkey,
+    pub owner: Pubkey,
+    pub keeper: Pubkey,
+    // Tokens minted to the owner by a buy, or lamports paid to the owner by a sell
+    pub amount_out: u64,
+    pub keeper_reward: u64,
+}
+
+#[event]
+pub struct OrderCancelled {
+    pub order: Pubkey,
+    pub token: Pubkey,
+    pub owner: Pubkey,
+}
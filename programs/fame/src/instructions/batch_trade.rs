@@ -0,0 +1,234 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::incinerator;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::{TokenInfo, BondingCurve, LiquidityPool, UserPortfolio, PriceObservations, GlobalState};
+use crate::errors::ErrorCode;
+use crate::instructions::mint_token::{buy, Purchase};
+use crate::instructions::burn_token::{sell, pay_out, Sale};
+use crate::instructions::verify_pool::require_solvent;
+
+#[derive(Accounts)]
+pub struct BatchTrade<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// CHECK: only receives the burned launch protection surplus
+    #[account(mut, address = incinerator::ID)]
+    pub incinerator: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    // Followed in the remaining accounts by the LEG_ACCOUNTS accounts of each leg, in leg order:
+    // token_info, bonding_curve, liquidity_pool, user_portfolio, mint, user_token_account and
+    // price_observations, all writable except the bonding curve
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TradeLeg {
+    pub mint: Pubkey,
+    pub side: TradeSide,
+    // Lamports spent by a buy, or tokens sold by a sell
+    pub amount: u64,
+    // Fewest tokens a buy may mint, or fewest lamports a sell may pay out
+    pub limit: u64,
+}
+
+pub const LEG_ACCOUNTS: usize = 7;
+// Three legs fit a legacy transaction, four take an address lookup table
+pub const MAX_BATCH_LEGS: usize = 4;
+
+// Trades the legs in order, as mint_token and burn_token would, and fails as a whole if any leg
+// fails or falls short of its limit. The user's token account must exist for every leg.
+pub fn batch_trade<'info>(ctx: Context<'_, '_, 'info, 'info, BatchTrade<'info>>, legs: Vec<TradeLeg>) -> Result<()> {
+    require!(!legs.is_empty() && legs.len() <= MAX_BATCH_LEGS, ErrorCode::InvalidBatch);
+    require!(ctx.remaining_accounts.len() == legs.len() * LEG_ACCOUNTS, ErrorCode::InvalidBatch);
+
+    // Sales are paid out once every leg's CPIs are done, as burn_token does
+    let mut payouts = Vec::with_capacity(legs.len());
+
+    for (leg, infos) in legs.iter().zip(ctx.remaining_accounts.chunks(LEG_ACCOUNTS)) {
+        let [token_info, bonding_curve, liquidity_pool, user_portfolio, mint, user_token_account, price_observations] = infos else {
+            return Err(ErrorCode::InvalidBatch.into());
+        };
+        let user = &ctx.accounts.user;
+        let liquidity_pool_info = liquidity_pool;
+
+        // The checks the MintToken and BurnToken constraints make
+        require_keys_eq!(mint.key(), leg.mint, ErrorCode::InvalidToken);
+        let (token_info_key, token_info_bump) = Pubkey::find_program_address(&[b"token_info", leg.mint.as_ref()], &crate::ID);
+        require_keys_eq!(token_info.key(), token_info_key, ErrorCode::InvalidToken);
+        let (price_observations_key, _) = Pubkey::find_program_address(&[b"price_observations", leg.mint.as_ref()], &crate::ID);
+        require_keys_eq!(price_observations.key(), price_observations_key, ErrorCode::InvalidToken);
+        let (user_portfolio_key, user_portfolio_bump) =
+            Pubkey::find_program_address(&[b"user_portfolio", user.key().as_ref(), leg.mint.as_ref()], &crate::ID);
+        require_keys_eq!(user_portfolio.key(), user_portfolio_key, ErrorCode::Unauthorized);
+        require_keys_eq!(user_token_account.key(), get_associated_token_address(&user.key(), &leg.mint), ErrorCode::Unauthorized);
+
+        let mut token_info = Account::<TokenInfo>::try_from(token_info)?;
+        let bonding_curve = Account::<BondingCurve>::try_from(bonding_curve)?;
+        let mut liquidity_pool = Account::<LiquidityPool>::try_from(liquidity_pool)?;
+        let mint = Account::<Mint>::try_from(mint)?;
+        let user_token_account = Account::<TokenAccount>::try_from(user_token_account)?;
+        let mut price_observations = Account::<PriceObservations>::try_from(price_observations)?;
+        require!(
+            token_info.mint == leg.mint && bonding_curve.token == leg.mint && liquidity_pool.token == leg.mint,
+            ErrorCode::InvalidToken
+        );
+
+        match leg.side {
+            TradeSide::Buy => {
+                // First buy of this token by the user
+                let first_buy = user_portfolio.data_is_empty();
+                if first_buy {
+                    let user_key = user.key();
+                    let signer_seeds: &[&[&[u8]]] =
+                        &[&[b"user_portfolio", user_key.as_ref(), leg.mint.as_ref(), &[user_portfolio_bump]]];
+                    create_pda(user, user_portfolio, UserPortfolio::LEN, signer_seeds, &ctx.accounts.system_program)?;
+                }
+                let mut user_portfolio = match first_buy {
+                    true => Account::<UserPortfolio>::try_from_unchecked(user_portfolio)?,
+                    false => Account::<UserPortfolio>::try_from(user_portfolio)?,
+                };
+
+                let tokens_minted = buy(
+                    Purchase {
+                        buyer: user,
+                        global_state: &ctx.accounts.global_state,
+                        token_info: &mut token_info,
+                        token_info_bump,
+                        bonding_curve: &bonding_curve,
+                        liquidity_pool: &mut liquidity_pool,
+                        user_portfolio: &mut user_portfolio,
+                        mint: &mint,
+                        token_account: &user_token_account,
+                        price_observations: &mut price_observations,
+                        incinerator: Some(&ctx.accounts.incinerator),
+                        token_program: &ctx.accounts.token_program,
+                        system_program: &ctx.accounts.system_program,
+                    },
+                    user.key(),
+                    leg.amount,
+                    &[],
+                )?;
+                require!(tokens_minted >= leg.limit, ErrorCode::SlippageExceeded);
+
+                token_info.exit(&crate::ID)?;
+                liquidity_pool.exit(&crate::ID)?;
+                user_portfolio.exit(&crate::ID)?;
+                price_observations.exit(&crate::ID)?;
+            }
+            TradeSide::Sell => {
+                let mut user_portfolio = Account::<UserPortfolio>::try_from(user_portfolio)?;
+                require_keys_eq!(user_portfolio.user, user.key(), ErrorCode::Unauthorized);
+
+                let amount_out = sell(
+                    Sale {
+                        seller: user,
+                        authority: user,
+                        authority_seeds: &[],
+                        global_state: &ctx.accounts.global_state,
+                        token_info: &mut token_info,
+                        bonding_curve: &bonding_curve,
+                        liquidity_pool: &mut liquidity_pool,
+                        user_portfolio: &mut user_portfolio,
+                        mint: &mint,
+                        token_account: &user_token_account,
+                        price_observations: &mut price_observations,
+                        token_program: &ctx.accounts.token_program,
+                    },
+                    leg.amount,
+                    false,
+                )?;
+                require!(amount_out >= leg.limit, ErrorCode::SlippageExceeded);
+
+                token_info.exit(&crate::ID)?;
+                liquidity_pool.exit(&crate::ID)?;
+                user_portfolio.exit(&crate::ID)?;
+                price_observations.exit(&crate::ID)?;
+                payouts.push((liquidity_pool_info, amount_out));
+            }
+        }
+    }
+
+    for (liquidity_pool, amount_out) in payouts {
+        pay_out(liquidity_pool, &ctx.accounts.user, amount_out)?;
+    }
+
+    // Every pool touched must still be able to buy back the supply it backs
+    for infos in ctx.remaining_accounts.chunks(LEG_ACCOUNTS) {
+        let token_info = Account::<TokenInfo>::try_from(&infos[0])?;
+        let bonding_curve = Account::<BondingCurve>::try_from(&infos[1])?;
+        let liquidity_pool = Account::<LiquidityPool>::try_from(&infos[2])?;
+        require_solvent(&bonding_curve, token_info.total_supply, &liquidity_pool)?;
+    }
+
+    Ok(())
+}
+
+// Creates the program account at the PDA `account` signed for by `signer_seeds`, funded by `payer`.
+// Like Anchor's init, it also takes over an address someone already sent lamports to, which
+// create_account refuses
+fn create_pda<'info>(
+    payer: &Signer<'info>,
+    account: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[&[u8]]],
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let lamports = account.lamports();
+    if lamports == 0 {
+        let cpi_context = CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::CreateAccount {
+                from: payer.to_account_info(),
+                to: account.clone(),
+            },
+            signer_seeds,
+        );
+        return system_program::create_account(cpi_context, rent, space as u64, &crate::ID);
+    }
+
+    let missing = rent.saturating_sub(lamports);
+    if missing > 0 {
+        let cpi_context = CpiContext::new(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: payer.to_account_info(),
+                to: account.clone(),
+            },
+        );
+        system_program::transfer(cpi_context, missing)?;
+    }
+    let cpi_context = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        system_program::Allocate {
+            account_to_allocate: account.clone(),
+        },
+        signer_seeds,
+    );
+    system_program::allocate(cpi_context, space as u64)?;
+    let cpi_context = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        system_program::Assign {
+            account_to_assign: account.clone(),
+        },
+        signer_seeds,
+    );
+    system_program::assign(cpi_context, &crate::ID)
+}
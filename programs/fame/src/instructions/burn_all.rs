@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::instructions::burn_token::{BurnToken, sell, close_token_account_of};
+
+// Sells the whole balance of the user's token account, and with `close_accounts` closes it and the
+// emptied user portfolio to refund their rent. Takes the burn_token accounts.
+pub fn burn_all(ctx: Context<BurnToken>, close_accounts: bool) -> Result<()> {
+    let amount_tokens = ctx.accounts.user_token_account.amount;
+
+    if amount_tokens == 0 && close_accounts {
+        // An empty account only needs closing
+        close_token_account_of(&ctx.accounts.user, &ctx.accounts.user_token_account, &ctx.accounts.token_program)?;
+    } else {
+        let amount_to_user = sell(ctx.accounts.sale(), amount_tokens, close_accounts)?;
+        ctx.accounts.settle(amount_to_user)?;
+    }
+
+    // The portfolio goes with the token account it tracks, but only once it counts nothing: a
+    // balance that left the account still counts towards the caps and open sell orders
+    if close_accounts {
+        require!(ctx.accounts.user_portfolio.balance == 0, ErrorCode::PortfolioNotEmpty);
+        ctx.accounts.user_portfolio.close(ctx.accounts.user.to_account_info())?;
+    }
+
+    Ok(())
+}
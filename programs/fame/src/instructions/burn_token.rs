@@ -1,13 +1,24 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::AccountMeta;
+use anchor_lang::solana_program::program::invoke;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
-use crate::state::{TokenInfo, BondingCurve, LiquidityPool, UserPortfolio};
+use anchor_spl::token::spl_token::{self, native_mint};
+use crate::state::{TokenInfo, BondingCurve, LiquidityPool, UserPortfolio, PriceObservations, GlobalState};
 use crate::errors::ErrorCode;
+use crate::instructions::verify_pool::require_solvent;
+use crate::events::{TokenBurned, PriceUpdate, FeeCollected};
 
 #[derive(Accounts)]
 pub struct BurnToken<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
     #[account(
         mut,
         constraint = token_info.mint == mint.key() @ ErrorCode::InvalidToken
@@ -27,6 +38,8 @@ pub struct BurnToken<'info> {
 
     #[account(
         mut,
+        seeds = [b"user_portfolio", user.key().as_ref(), mint.key().as_ref()],
+        bump,
         constraint = user_portfolio.user == user.key() @ ErrorCode::Unauthorized,
         constraint = user_portfolio.token == mint.key() @ ErrorCode::InvalidToken
     )]
@@ -42,22 +55,119 @@ pub struct BurnToken<'info> {
     )]
     pub user_token_account: Account<'info, TokenAccount>,
 
+    #[account(
+        mut,
+        seeds = [b"price_observations", mint.key().as_ref()],
+        bump
+    )]
+    pub price_observations: Box<Account<'info, PriceObservations>>,
+
+    // Wrapped SOL account to pay the proceeds into instead of the user's lamports
+    #[account(
+        mut,
+        constraint = wsol_account.mint == native_mint::ID @ ErrorCode::InvalidWsolAccount
+    )]
+    pub wsol_account: Option<Box<Account<'info, TokenAccount>>>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn burn_token(ctx: Context<BurnToken>, amount_tokens: u64) -> Result<()> {
-    let token_info = &mut ctx.accounts.token_info;
-    let bonding_curve = &ctx.accounts.bonding_curve;
-    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
-    let user_portfolio = &mut ctx.accounts.user_portfolio;
-    let user = &ctx.accounts.user;
+    let amount_to_user = sell(ctx.accounts.sale(), amount_tokens, false)?;
+    ctx.accounts.settle(amount_to_user)
+}
+
+impl<'info> BurnToken<'info> {
+    pub fn sale(&mut self) -> Sale<'_, 'info> {
+        Sale {
+            seller: &self.user,
+            authority: &self.user,
+            authority_seeds: &[],
+            global_state: &self.global_state,
+            token_info: &mut self.token_info,
+            bonding_curve: &self.bonding_curve,
+            liquidity_pool: &mut self.liquidity_pool,
+            user_portfolio: &mut self.user_portfolio,
+            mint: &self.mint,
+            token_account: &self.user_token_account,
+            price_observations: &mut self.price_observations,
+            token_program: &self.token_program,
+        }
+    }
+
+    pub fn settle(&self, amount_to_user: u64) -> Result<()> {
+        let Some(wsol_account) = &self.wsol_account else {
+            return settle_sale(&self.user, &self.liquidity_pool, &self.bonding_curve, self.token_info.total_supply, amount_to_user);
+        };
+
+        let wsol_account = wsol_account.to_account_info();
+        settle_sale(&wsol_account, &self.liquidity_pool, &self.bonding_curve, self.token_info.total_supply, amount_to_user)?;
+
+        // Wrap the lamports paid in. The pool rides along in the CPI, which the token program
+        // ignores, so that the runtime sees both sides of the move
+        let liquidity_pool = self.liquidity_pool.to_account_info();
+        let mut instruction = spl_token::instruction::sync_native(&token::ID, wsol_account.key)?;
+        instruction.accounts.push(AccountMeta::new(liquidity_pool.key(), false));
+        invoke(&instruction, &[wsol_account, liquidity_pool, self.token_program.to_account_info()])?;
+        Ok(())
+    }
+}
+
+// Accounts a curve sale touches, shared by the instructions that sell to the curve
+pub struct Sale<'a, 'info> {
+    pub seller: &'a AccountInfo<'info>,
+    // Signs the burn: the seller, or a PDA they delegated to with its seeds
+    pub authority: &'a AccountInfo<'info>,
+    pub authority_seeds: &'a [&'a [&'a [u8]]],
+    pub global_state: &'a GlobalState,
+    pub token_info: &'a mut Account<'info, TokenInfo>,
+    pub bonding_curve: &'a BondingCurve,
+    pub liquidity_pool: &'a mut Account<'info, LiquidityPool>,
+    pub user_portfolio: &'a mut Account<'info, UserPortfolio>,
+    pub mint: &'a Account<'info, Mint>,
+    pub token_account: &'a Account<'info, TokenAccount>,
+    pub price_observations: &'a mut Account<'info, PriceObservations>,
+    pub token_program: &'a Program<'info, Token>,
+}
+
+// Sells `amount_tokens` back to the curve, closing the emptied token account on the way if asked,
+// and returns the lamports owed to the seller, which settle_sale pays out after the last CPI
+pub fn sell(accounts: Sale, amount_tokens: u64, close_token_account: bool) -> Result<u64> {
+    let Sale {
+        seller: user,
+        authority,
+        authority_seeds,
+        global_state,
+        token_info,
+        bonding_curve,
+        liquidity_pool,
+        user_portfolio,
+        mint,
+        token_account,
+        price_observations,
+        token_program,
+    } = accounts;
+
+    require!(!global_state.paused, ErrorCode::TradingPaused);
+    require!(amount_tokens > 0, ErrorCode::InvalidBurnAmount);
+    require!(
+        global_state.max_sell_tokens == 0 || amount_tokens <= global_state.max_sell_tokens,
+        ErrorCode::SellLimitExceeded
+    );
+    require!(user_portfolio.balance >= amount_tokens, ErrorCode::InsufficientBalance);
+
+    // Record the price that held until this trade
+    let clock = Clock::get()?;
+    let spot_price = bonding_curve.calculate_price(token_info.total_supply)?;
+    price_observations.record(clock.unix_timestamp, spot_price)?;
 
     // Calculate the amount of SOL to return based on the bonding curve
     let sol_to_return = calculate_sol_to_return(bonding_curve, token_info.total_supply, amount_tokens)?;
+    liquidity_pool.record_volume(clock.slot, sol_to_return, global_state.max_slot_volume)?;
 
-    // Calculate fee (1% of the transaction volume)
-    let fee = sol_to_return / 100;
+    // Calculate fee (sell fee share of the transaction volume)
+    let fee = global_state.sell_fee(sol_to_return);
     let amount_to_user = sol_to_return - fee;
 
     // Ensure liquidity pool has enough balance
@@ -77,53 +187,101 @@ pub fn burn_token(ctx: Context<BurnToken>, amount_tokens: u64) -> Result<()> {
     user_portfolio.balance = user_portfolio.balance.checked_sub(amount_tokens)
         .ok_or(ErrorCode::ArithmeticOverflow)?;
 
-    // Transfer SOL from program to user
-    **liquidity_pool.to_account_info().try_borrow_mut_lamports()? -= amount_to_user;
-    **user.to_account_info().try_borrow_mut_lamports()? += amount_to_user;
-
     // Burn tokens from user
     let cpi_accounts = token::Burn {
-        mint: ctx.accounts.mint.to_account_info(),
-        from: ctx.accounts.user_token_account.to_account_info(),
-        authority: user.to_account_info(),
+        mint: mint.to_account_info(),
+        from: token_account.to_account_info(),
+        authority: authority.to_account_info(),
     };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    let cpi_program = token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, authority_seeds);
     token::burn(cpi_ctx, amount_tokens)?;
+    if close_token_account {
+        close_token_account_of(user, token_account, token_program)?;
+    }
+
+    emit!(FeeCollected {
+        token: mint.key(),
+        amount: fee,
+    });
+
+    emit!(TokenBurned {
+        token: mint.key(),
+        user: user.key(),
+        amount: amount_tokens,
+        refund: amount_to_user,
+    });
+
+    emit!(PriceUpdate {
+        token: mint.key(),
+        new_price: bonding_curve.calculate_price(token_info.total_supply)?,
+    });
+
+    Ok(amount_to_user)
+}
+
+// Pays a sale out of the pool. Direct lamport moves must follow every CPI that does not take both
+// accounts, as the runtime only carries the accounts a CPI takes over into it and would find the
+// rest unbalanced.
+pub fn settle_sale(
+    recipient: &AccountInfo,
+    liquidity_pool: &Account<LiquidityPool>,
+    bonding_curve: &BondingCurve,
+    total_supply: u64,
+    amount_to_user: u64,
+) -> Result<()> {
+    pay_out(&liquidity_pool.to_account_info(), recipient, amount_to_user)?;
 
+    // The pool must still be able to buy back the supply it backs
+    require_solvent(bonding_curve, total_supply, liquidity_pool)
+}
+
+pub fn pay_out(liquidity_pool: &AccountInfo, recipient: &AccountInfo, amount: u64) -> Result<()> {
+    **liquidity_pool.try_borrow_mut_lamports()? -= amount;
+    **recipient.try_borrow_mut_lamports()? += amount;
     Ok(())
 }
 
-fn calculate_sol_to_return(bonding_curve: &BondingCurve, current_supply: u64, amount_tokens: u64) -> Result<u64> {
-    // Convert parameters to f64 for precise calculations
-    let p0 = bonding_curve.initial_price as f64 / 1e9; // Convert to SOL (assuming 9 decimal places)
-    let k = bonding_curve.slope as f64 / 1e6; // Assuming slope is stored as an integer representation of 0.0000921
-    let current_supply = current_supply as f64;
-    let amount_tokens = amount_tokens as f64;
+// Refunds the rent of the user's emptied token account to the user
+pub fn close_token_account_of<'info>(
+    user: &AccountInfo<'info>,
+    token_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let cpi_accounts = token::CloseAccount {
+        account: token_account.to_account_info(),
+        destination: user.to_account_info(),
+        authority: user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+    token::close_account(cpi_ctx)
+}
 
-    // Check for division by zero and invalid parameters
-    if k == 0.0 || current_supply < amount_tokens {
+pub fn calculate_sol_to_return(bonding_curve: &BondingCurve, current_supply: u64, amount_tokens: u64) -> Result<u64> {
+    // Check for invalid parameters before losing precision to f64
+    if bonding_curve.initial_price == 0 || bonding_curve.slope == 0 || current_supply < amount_tokens {
         return Err(ErrorCode::InvalidBondingCurveParameters.into());
     }
+    if amount_tokens == 0 {
+        return Ok(0);
+    }
 
-    // Calculate the integral of the bonding curve function
-    // The integral of P(n) = P₀ * e^(k * n) is (P₀ / k) * (e^(k * n) - 1)
-    let integral = |n: f64| -> f64 {
-        (p0 / k) * ((k * n).exp() - 1.0)
-    };
-
-    // Calculate the amount of SOL to return
-    let start_integral = integral(current_supply);
-    let end_integral = integral(current_supply - amount_tokens);
-    let sol_to_return = start_integral - end_integral;
+    // Convert parameters to f64 for precise calculations
+    let p0 = bonding_curve.initial_price as f64 / 1e9; // Convert to SOL (assuming 9 decimal places)
+    let k = bonding_curve.slope as f64 / 1e6; // Assuming slope is stored as an integer representation of 0.0000921
+    let remaining_supply = (current_supply - amount_tokens) as f64;
+    let amount_tokens = amount_tokens as f64;
 
-    // Check for underflow
-    if sol_to_return < 0.0 {
-        return Err(ErrorCode::ArithmeticUnderflow.into());
-    }
+    // The integral of P(n) = P₀ * e^(k * n) is I(n) = (P₀ / k) * (e^(k * n) - 1).
+    // I(s) - I(s - t) = (P₀ / k) * e^(k * (s - t)) * (e^(k * t) - 1), which avoids
+    // subtracting two large integrals from each other
+    let sol_to_return = (p0 / k) * (k * remaining_supply).exp() * (k * amount_tokens).exp_m1();
 
     // Convert back to u64 (lamports), rounding down
-    let lamports_to_return = (sol_to_return * 1e9).floor() as u64;
+    let lamports_to_return = sol_to_return * 1e9;
+    if !lamports_to_return.is_finite() || lamports_to_return >= u64::MAX as f64 {
+        return Err(ErrorCode::ArithmeticOverflow.into());
+    }
 
-    Ok(lamports_to_return)
-}
\ No newline at end of file
+    Ok(lamports_to_return.floor() as u64)
+}
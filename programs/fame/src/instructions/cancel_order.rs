@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+use crate::state::Order;
+use crate::errors::ErrorCode;
+use crate::events::OrderCancelled;
+use crate::instructions::batch_trade::TradeSide;
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    // Closing the order refunds a buy's escrow along with the rent
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner @ ErrorCode::Unauthorized
+    )]
+    pub order: Account<'info, Order>,
+
+    #[account(
+        mut,
+        associated_token::mint = order.mint,
+        associated_token::authority = order
+    )]
+    pub order_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = order.mint,
+        associated_token::authority = owner
+    )]
+    pub user_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+// Cancels an unfilled order, returning what it escrows to the owner
+pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
+    let order = &ctx.accounts.order;
+    require!(
+        ctx.accounts.order_token_account.is_some() == (order.side == TradeSide::Sell),
+        ErrorCode::InvalidOrder
+    );
+
+    // A sell order's tokens go back to the owner's token account. The portfolio counted them
+    // while escrowed, so it stays as it is
+    if let Some(order_token_account) = &ctx.accounts.order_token_account {
+        let user_token_account = ctx.accounts.user_token_account.as_ref().ok_or(ErrorCode::InvalidOrder)?;
+        let id = order.id.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"order", order.owner.as_ref(), order.mint.as_ref(), &id, &[order.bump]]];
+        let token_program = ctx.accounts.token_program.to_account_info();
+
+        let cpi_accounts = token::Transfer {
+            from: order_token_account.to_account_info(),
+            to: user_token_account.to_account_info(),
+            authority: order.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer_seeds),
+            order_token_account.amount,
+        )?;
+        let cpi_accounts = token::CloseAccount {
+            account: order_token_account.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: order.to_account_info(),
+        };
+        token::close_account(CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds))?;
+    }
+
+    emit!(OrderCancelled {
+        order: order.key(),
+        token: order.mint,
+        owner: order.owner,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::state::{UserPortfolio, Vesting};
+use crate::errors::ErrorCode;
+use crate::events::VestedClaimed;
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting", mint.key().as_ref()],
+        bump,
+        constraint = vesting.beneficiary == beneficiary.key() @ ErrorCode::Unauthorized,
+        constraint = vesting.token == mint.key() @ ErrorCode::InvalidToken
+    )]
+    pub vesting: Account<'info, Vesting>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting
+    )]
+    pub vesting_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary
+    )]
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        space = UserPortfolio::LEN,
+        seeds = [b"user_portfolio", beneficiary.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub beneficiary_portfolio: Account<'info, UserPortfolio>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+    let vesting = &mut ctx.accounts.vesting;
+    let beneficiary_portfolio = &mut ctx.accounts.beneficiary_portfolio;
+    let beneficiary = &ctx.accounts.beneficiary;
+
+    // Everything vested so far that has not been claimed yet
+    let vested = vesting.vested_amount(Clock::get()?.unix_timestamp);
+    let amount = vested.checked_sub(vesting.claimed_amount)
+        .ok_or(ErrorCode::ArithmeticUnderflow)?;
+    require!(amount > 0, ErrorCode::NothingToClaim);
+
+    vesting.claimed_amount = vested;
+
+    if beneficiary_portfolio.user == Pubkey::default() {
+        beneficiary_portfolio.user = beneficiary.key();
+        beneficiary_portfolio.token = ctx.accounts.mint.key();
+    }
+    beneficiary_portfolio.balance = beneficiary_portfolio.balance.checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    // Release the tokens, signed by the vesting PDA that owns them
+    let mint_key = ctx.accounts.mint.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"vesting", mint_key.as_ref(), &[ctx.bumps.vesting]]];
+    let cpi_accounts = token::Transfer {
+        from: ctx.accounts.vesting_token_account.to_account_info(),
+        to: ctx.accounts.beneficiary_token_account.to_account_info(),
+        authority: vesting.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+    token::transfer(cpi_ctx, amount)?;
+
+    emit!(VestedClaimed {
+        token: mint_key,
+        beneficiary: beneficiary.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -1,15 +1,24 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, Mint, TokenAccount};
+use anchor_spl::token::{self, Token, Mint, TokenAccount};
+use anchor_spl::token::spl_token::instruction::AuthorityType;
 use anchor_spl::associated_token::AssociatedToken;
-use crate::state::{TokenInfo, BondingCurve, LiquidityPool};
+use crate::state::{TokenInfo, BondingCurve, LiquidityPool, PriceObservations, UserPortfolio, GlobalState, Vesting, LaunchProtection, HoldingLimit, Presale};
 use crate::errors::ErrorCode;
 use crate::events::TokenCreated;
+use crate::instructions::mint_token::{purchase, Purchase};
+use crate::instructions::burn_token::calculate_sol_to_return;
 
 
 #[derive(Accounts)]
 pub struct CreateToken<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
     
     #[account(
         init,
@@ -30,23 +39,65 @@ pub struct CreateToken<'info> {
     #[account(
         init,
         payer = creator,
-        space = TokenInfo::LEN
+        space = TokenInfo::LEN,
+        seeds = [b"token_info", mint.key().as_ref()],
+        bump
     )]
     pub token_info: Account<'info, TokenInfo>,
     
     #[account(
         init,
         payer = creator,
-        space = BondingCurve::LEN
+        space = BondingCurve::LEN,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump
     )]
     pub bonding_curve: Account<'info, BondingCurve>,
     
     #[account(
         init,
         payer = creator,
-        space = LiquidityPool::LEN
+        space = LiquidityPool::LEN,
+        seeds = [b"liquidity_pool", mint.key().as_ref()],
+        bump
     )]
     pub liquidity_pool: Account<'info, LiquidityPool>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = PriceObservations::LEN,
+        seeds = [b"price_observations", mint.key().as_ref()],
+        bump
+    )]
+    pub price_observations: Box<Account<'info, PriceObservations>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = UserPortfolio::LEN,
+        seeds = [b"user_portfolio", creator.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub creator_portfolio: Account<'info, UserPortfolio>,
+
+    // Only with a creator allocation
+    #[account(
+        init,
+        payer = creator,
+        space = Vesting::LEN,
+        seeds = [b"vesting", mint.key().as_ref()],
+        bump
+    )]
+    pub vesting: Option<Box<Account<'info, Vesting>>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = vesting,
+    )]
+    pub vesting_token_account: Option<Box<Account<'info, TokenAccount>>>,
     
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -54,6 +105,15 @@ pub struct CreateToken<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CreatorAllocation {
+    // Share of the curve's max supply, in basis points
+    pub supply_bps: u16,
+    // Seconds after creation before anything vests, and until everything has
+    pub cliff_seconds: i64,
+    pub duration_seconds: i64,
+}
+
 #[derive(Accounts)]
 pub struct UpdateBondingCurveParams<'info> {
     #[account(mut)]
@@ -67,16 +127,51 @@ pub struct UpdateBondingCurveParams<'info> {
 }
 
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_token(
     ctx: Context<CreateToken>,
     name: String,
     symbol: String,
     social_account_url: String,
+    initial_buy_sol: Option<u64>,
+    creator_allocation: Option<CreatorAllocation>,
+    launch_at: Option<i64>,
+    launch_protection: Option<LaunchProtection>,
+    holding_limit: Option<HoldingLimit>,
+    presale: Option<Presale>,
 ) -> Result<()> {
     // Validate input
     require!(name.len() <= 32, ErrorCode::InvalidTokenName);
     require!(symbol.len() <= 10, ErrorCode::InvalidTokenSymbol);
     require!(social_account_url.len() <= 200, ErrorCode::InvalidSocialAccountUrl);
+    if initial_buy_sol.is_some() {
+        require!(!ctx.accounts.global_state.paused, ErrorCode::TradingPaused);
+    }
+    require!(initial_buy_sol != Some(0), ErrorCode::InvalidMintAmount);
+    if let Some(allocation) = &creator_allocation {
+        require!(
+            allocation.supply_bps > 0 && allocation.supply_bps <= Vesting::MAX_ALLOCATION_BPS,
+            ErrorCode::InvalidAllocation
+        );
+        require!(
+            allocation.cliff_seconds >= 0
+                && allocation.duration_seconds > 0
+                && allocation.cliff_seconds <= allocation.duration_seconds,
+            ErrorCode::InvalidVestingSchedule
+        );
+    }
+    if let Some(protection) = &launch_protection {
+        protection.validate()?;
+    }
+    if let Some(limit) = &holding_limit {
+        limit.validate()?;
+    }
+    // The vesting accounts come with an allocation and only with one
+    require!(
+        creator_allocation.is_some() == ctx.accounts.vesting.is_some()
+            && creator_allocation.is_some() == ctx.accounts.vesting_token_account.is_some(),
+        ErrorCode::InvalidAllocation
+    );
 
     // Initialize accounts
     let token_info = &mut ctx.accounts.token_info;
@@ -98,12 +193,124 @@ pub fn create_token(
     bonding_curve.initial_price = 10_000_000; // 0.01 SOL (assuming 9 decimals)
     bonding_curve.slope = 92; // This represents 0.0000921 in our calculation
     bonding_curve.admin = creator.key();
+    bonding_curve.max_supply = BondingCurve::DEFAULT_MAX_SUPPLY;
+    bonding_curve.allocated_supply = 0;
 
     // Set up LiquidityPool
     liquidity_pool.token = mint.key();
     liquidity_pool.balance = 0;
     liquidity_pool.accumulated_fees = 0;
     liquidity_pool.authority = creator.key();
+    liquidity_pool.volume_slot = 0;
+    liquidity_pool.slot_volume = 0;
+
+    // Hand the mint authority over to the token_info PDA so only the curve can mint
+    let cpi_accounts = token::SetAuthority {
+        current_authority: creator.to_account_info(),
+        account_or_mint: mint.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::set_authority(cpi_ctx, AuthorityType::MintTokens, Some(token_info.key()))?;
+
+    // Start the price history at creation time
+    let now = Clock::get()?.unix_timestamp;
+    // Open the curve at the scheduled time, or right away
+    token_info.launch_at = launch_at.unwrap_or(now);
+    // The protection window opens now, or with the first buy of a scheduled launch
+    token_info.launch_protection = launch_protection;
+    token_info.holding_limit = holding_limit;
+    // Public trading opens after the presale, if any
+    if let Some(presale) = &presale {
+        require!(presale.ends_at > token_info.launch_at && presale.max_per_wallet > 0, ErrorCode::InvalidPresale);
+    }
+    token_info.presale = presale;
+    token_info.launch_slot = if token_info.launch_at <= now && presale.is_none() { Clock::get()?.slot } else { 0 };
+    ctx.accounts
+        .price_observations
+        .initialize(mint.key(), now);
+
+    // Lock the creator allocation at the bottom of the curve, so buyers start above it. The creator
+    // pays the curve price for it into the pool, so that selling it back draws on its own reserves
+    // rather than the buyers'
+    let mut allocated = 0;
+    if let (Some(allocation), Some(vesting), Some(vesting_token_account)) = (
+        creator_allocation,
+        ctx.accounts.vesting.as_deref_mut(),
+        ctx.accounts.vesting_token_account.as_deref(),
+    ) {
+        allocated = (bonding_curve.max_supply as u128 * allocation.supply_bps as u128 / 10_000) as u64;
+        require!(allocated > 0, ErrorCode::InvalidAllocation);
+
+        vesting.token = mint.key();
+        vesting.beneficiary = creator.key();
+        vesting.total_amount = allocated;
+        vesting.claimed_amount = 0;
+        vesting.start_timestamp = now;
+        vesting.cliff_seconds = allocation.cliff_seconds;
+        vesting.duration_seconds = allocation.duration_seconds;
+
+        bonding_curve.allocated_supply = allocated;
+        token_info.total_supply = allocated;
+
+        let reserves = calculate_sol_to_return(bonding_curve, allocated, allocated)?;
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: creator.to_account_info(),
+                to: liquidity_pool.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, reserves)?;
+        liquidity_pool.balance = reserves;
+
+        let mint_key = mint.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"token_info", mint_key.as_ref(), &[ctx.bumps.token_info]]];
+        let cpi_accounts = token::MintTo {
+            mint: mint.to_account_info(),
+            to: vesting_token_account.to_account_info(),
+            authority: token_info.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+        token::mint_to(cpi_ctx, allocated)?;
+    }
+
+    // The creator's portfolio exists from the start, so the initial buy cannot be raced
+    let creator_portfolio = &mut ctx.accounts.creator_portfolio;
+    creator_portfolio.user = creator.key();
+    creator_portfolio.token = mint.key();
+
+    // The creator's own buy stays within the holding limit like any other wallet's
+    let holding_cap = token_info.holding_limit.and_then(|limit| {
+        limit.cap_at(bonding_curve.max_supply, token_info.total_supply, now)
+    });
+
+    // Buy from the curve in the same transaction, before anyone else can, even ahead of launch
+    let initial_buy_tokens = match initial_buy_sol {
+        Some(amount_sol) => purchase(
+            Purchase {
+                buyer: creator,
+                global_state: &ctx.accounts.global_state,
+                token_info,
+                token_info_bump: ctx.bumps.token_info,
+                bonding_curve,
+                liquidity_pool,
+                user_portfolio: creator_portfolio,
+                mint,
+                token_account: &ctx.accounts.creator_token_account,
+                price_observations: &mut ctx.accounts.price_observations,
+                incinerator: None,
+                token_program: &ctx.accounts.token_program,
+                system_program: &ctx.accounts.system_program,
+            },
+            amount_sol,
+            // The creator's own buy is not sniping
+            ctx.accounts.global_state.buy_fee_bps,
+        )?,
+        None => 0,
+    };
+    if let Some(cap) = holding_cap {
+        require!(initial_buy_tokens <= cap, ErrorCode::HoldingLimitExceeded);
+    }
 
     // Emit TokenCreated event
     emit!(TokenCreated {
@@ -112,6 +319,10 @@ pub fn create_token(
         symbol,
         social_account_url,
         creator: creator.key(),
+        initial_buy_sol: initial_buy_sol.unwrap_or(0),
+        initial_buy_tokens,
+        creator_allocation: allocated,
+        launch_at: token_info.launch_at,
     });
 
     Ok(())
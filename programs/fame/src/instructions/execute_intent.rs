@@ -0,0 +1,285 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{ed25519_program, incinerator};
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::associated_token::AssociatedToken;
+use crate::state::{TokenInfo, BondingCurve, LiquidityPool, UserPortfolio, PriceObservations, GlobalState, IntentNonces};
+use crate::errors::ErrorCode;
+use crate::events::IntentExecuted;
+use crate::instructions::mint_token::{buy, unwrap_wsol, Purchase};
+use crate::instructions::burn_token::{sell, settle_sale, Sale};
+use crate::instructions::batch_trade::TradeSide;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TradeIntent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub side: TradeSide,
+    // Lamports spent by a buy, or tokens sold by a sell
+    pub amount: u64,
+    // Fewest tokens a buy may mint, or fewest lamports a sell may pay out
+    pub limit: u64,
+    pub nonce: u64,
+    // Unix timestamp after which the intent can no longer be executed
+    pub expiry: i64,
+}
+
+impl TradeIntent {
+    pub const DOMAIN: &'static [u8] = b"fame trade intent";
+
+    // Bytes the user signs: the domain and program id, so that the signature passes for nothing
+    // else, followed by the intent
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = Vec::with_capacity(Self::DOMAIN.len() + 32 + 32 + 32 + 1 + 8 * 4);
+        message.extend_from_slice(Self::DOMAIN);
+        message.extend_from_slice(crate::ID.as_ref());
+        message.extend_from_slice(self.user.as_ref());
+        message.extend_from_slice(self.mint.as_ref());
+        message.push(self.side as u8);
+        message.extend_from_slice(&self.amount.to_le_bytes());
+        message.extend_from_slice(&self.limit.to_le_bytes());
+        message.extend_from_slice(&self.nonce.to_le_bytes());
+        message.extend_from_slice(&self.expiry.to_le_bytes());
+        message
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(intent: TradeIntent)]
+pub struct ExecuteIntent<'info> {
+    // Submits the intent, paying the transaction fee and the rent of any account it creates
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// CHECK: the wallet that signed the intent, only receiving the proceeds of a sell
+    #[account(mut, address = intent.user @ ErrorCode::Unauthorized)]
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = IntentNonces::LEN,
+        seeds = [b"intent_nonces", user.key().as_ref()],
+        bump
+    )]
+    pub intent_nonces: Box<Account<'info, IntentNonces>>,
+
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [b"token_info", mint.key().as_ref()],
+        bump,
+        constraint = token_info.mint == mint.key() @ ErrorCode::InvalidToken
+    )]
+    pub token_info: Box<Account<'info, TokenInfo>>,
+
+    #[account(
+        constraint = bonding_curve.token == mint.key() @ ErrorCode::InvalidToken
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        constraint = liquidity_pool.token == mint.key() @ ErrorCode::InvalidToken
+    )]
+    pub liquidity_pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = UserPortfolio::LEN,
+        seeds = [b"user_portfolio", user.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub user_portfolio: Box<Account<'info, UserPortfolio>>,
+
+    #[account(mut, address = intent.mint @ ErrorCode::InvalidToken)]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        associated_token::mint = mint,
+        associated_token::authority = user
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"price_observations", mint.key().as_ref()],
+        bump
+    )]
+    pub price_observations: Box<Account<'info, PriceObservations>>,
+
+    /// CHECK: only receives the burned launch protection surplus
+    #[account(mut, address = incinerator::ID)]
+    pub incinerator: UncheckedAccount<'info>,
+
+    // A buy pays from the user's wrapped SOL account, unwrapped like mint_token does it
+    #[account(
+        mut,
+        constraint = wsol_account.mint == native_mint::ID @ ErrorCode::InvalidWsolAccount,
+        constraint = wsol_account.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub wsol_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        init,
+        payer = relayer,
+        seeds = [b"wsol_unwrap", user.key().as_ref()],
+        bump,
+        token::mint = native_mint,
+        token::authority = token_info
+    )]
+    pub wsol_unwrap: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(address = native_mint::ID @ ErrorCode::InvalidWsolAccount)]
+    pub native_mint: Option<Box<Account<'info, Mint>>>,
+
+    /// CHECK: the instructions sysvar, to find the signature check of the intent
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+// Trades for a user who signed `intent` off-chain, so that a relayer can submit it and pay the
+// fees. The instruction right before this one must be the Ed25519 program's check of the user's
+// signature of the intent's message. The program trades from the user's token accounts as the
+// delegate the user approved on them: their wrapped SOL account for a buy, and their token account
+// of the mint for a sell.
+pub fn execute_intent(ctx: Context<ExecuteIntent>, intent: TradeIntent) -> Result<()> {
+    require!(Clock::get()?.unix_timestamp <= intent.expiry, ErrorCode::IntentExpired);
+    verify_signature(&ctx.accounts.instructions, &intent.user, &intent.message())?;
+
+    let accounts = ctx.accounts;
+    let intent_nonces = &mut accounts.intent_nonces;
+    if intent_nonces.user == Pubkey::default() {
+        intent_nonces.user = intent.user;
+    }
+    intent_nonces.spend(intent.nonce)?;
+
+    let user = accounts.user.to_account_info();
+    let delegate = accounts.intent_nonces.to_account_info();
+    let delegate_seeds: &[&[&[u8]]] = &[&[b"intent_nonces", intent.user.as_ref(), &[ctx.bumps.intent_nonces]]];
+
+    let amount_out = match intent.side {
+        TradeSide::Buy => {
+            let (Some(wsol_account), Some(wsol_unwrap), Some(_)) =
+                (&accounts.wsol_account, &accounts.wsol_unwrap, &accounts.native_mint)
+            else {
+                return Err(ErrorCode::InvalidWsolAccount.into());
+            };
+            // The relayer fronts the payment and gets it back from the user's wrapped SOL
+            unwrap_wsol(
+                token::Transfer {
+                    from: wsol_account.to_account_info(),
+                    to: wsol_unwrap.to_account_info(),
+                    authority: delegate,
+                },
+                delegate_seeds,
+                accounts.relayer.to_account_info(),
+                &accounts.token_info,
+                ctx.bumps.token_info,
+                &accounts.token_program,
+                intent.amount,
+            )?;
+
+            buy(
+                Purchase {
+                    buyer: &accounts.relayer,
+                    global_state: &accounts.global_state,
+                    token_info: &mut accounts.token_info,
+                    token_info_bump: ctx.bumps.token_info,
+                    bonding_curve: &accounts.bonding_curve,
+                    liquidity_pool: &mut accounts.liquidity_pool,
+                    user_portfolio: &mut accounts.user_portfolio,
+                    mint: &accounts.mint,
+                    token_account: &accounts.user_token_account,
+                    price_observations: &mut accounts.price_observations,
+                    incinerator: Some(&accounts.incinerator),
+                    token_program: &accounts.token_program,
+                    system_program: &accounts.system_program,
+                },
+                intent.user,
+                intent.amount,
+                &[],
+            )?
+        }
+        TradeSide::Sell => {
+            require_keys_eq!(accounts.user_portfolio.user, intent.user, ErrorCode::Unauthorized);
+            let amount_to_user = sell(
+                Sale {
+                    seller: &user,
+                    authority: &delegate,
+                    authority_seeds: delegate_seeds,
+                    global_state: &accounts.global_state,
+                    token_info: &mut accounts.token_info,
+                    bonding_curve: &accounts.bonding_curve,
+                    liquidity_pool: &mut accounts.liquidity_pool,
+                    user_portfolio: &mut accounts.user_portfolio,
+                    mint: &accounts.mint,
+                    token_account: &accounts.user_token_account,
+                    price_observations: &mut accounts.price_observations,
+                    token_program: &accounts.token_program,
+                },
+                intent.amount,
+                false,
+            )?;
+            settle_sale(
+                &user,
+                &accounts.liquidity_pool,
+                &accounts.bonding_curve,
+                accounts.token_info.total_supply,
+                amount_to_user,
+            )?;
+            amount_to_user
+        }
+    };
+    require!(amount_out >= intent.limit, ErrorCode::SlippageExceeded);
+
+    emit!(IntentExecuted {
+        token: intent.mint,
+        user: intent.user,
+        relayer: accounts.relayer.key(),
+        nonce: intent.nonce,
+        amount_out,
+    });
+
+    Ok(())
+}
+
+// The instruction before the current one must be an Ed25519 program instruction checking one
+// signature by `user` of `message`, with the key, signature and message all in its own data
+fn verify_signature(instructions: &AccountInfo, user: &Pubkey, message: &[u8]) -> Result<()> {
+    let index = load_current_index_checked(instructions)?;
+    require!(index > 0, ErrorCode::InvalidIntentSignature);
+    let instruction = load_instruction_at_checked(index as usize - 1, instructions)?;
+    require_keys_eq!(instruction.program_id, ed25519_program::ID, ErrorCode::InvalidIntentSignature);
+
+    // Two header bytes, then the offsets of the one signature as seven u16 values
+    let data = &instruction.data;
+    require!(data.len() >= 16 && data[0] == 1, ErrorCode::InvalidIntentSignature);
+    let offset = |field: usize| u16::from_le_bytes([data[2 + 2 * field], data[3 + 2 * field]]) as usize;
+    let own_data = u16::MAX as usize;
+    require!(
+        offset(1) == own_data && offset(3) == own_data && offset(6) == own_data,
+        ErrorCode::InvalidIntentSignature
+    );
+
+    let public_key = data.get(offset(2)..offset(2) + 32);
+    let signed = data.get(offset(4)..offset(4) + offset(5));
+    require!(
+        public_key == Some(user.as_ref()) && signed == Some(message),
+        ErrorCode::InvalidIntentSignature
+    );
+    Ok(())
+}
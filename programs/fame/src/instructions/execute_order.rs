@@ -0,0 +1,197 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::incinerator;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use crate::state::{TokenInfo, BondingCurve, LiquidityPool, UserPortfolio, PriceObservations, GlobalState, Order, fee_for};
+use crate::errors::ErrorCode;
+use crate::events::OrderExecuted;
+use crate::instructions::mint_token::{buy, Purchase};
+use crate::instructions::burn_token::{sell, settle_sale, pay_out, Sale};
+use crate::instructions::batch_trade::TradeSide;
+
+#[derive(Accounts)]
+pub struct ExecuteOrder<'info> {
+    // Anyone may fill a triggered order. The keeper fronts a buy and is repaid from the escrow
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// CHECK: the order's owner, receiving the proceeds and the refunded rent
+    #[account(mut, address = order.owner @ ErrorCode::Unauthorized)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"order", order.owner.as_ref(), order.mint.as_ref(), &order.id.to_le_bytes()],
+        bump = order.bump
+    )]
+    pub order: Box<Account<'info, Order>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = order
+    )]
+    pub order_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [b"token_info", mint.key().as_ref()],
+        bump,
+        constraint = token_info.mint == mint.key() @ ErrorCode::InvalidToken
+    )]
+    pub token_info: Box<Account<'info, TokenInfo>>,
+
+    #[account(
+        constraint = bonding_curve.token == mint.key() @ ErrorCode::InvalidToken
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        constraint = liquidity_pool.token == mint.key() @ ErrorCode::InvalidToken
+    )]
+    pub liquidity_pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(
+        mut,
+        seeds = [b"user_portfolio", order.owner.as_ref(), mint.key().as_ref()],
+        bump,
+        constraint = user_portfolio.user == order.owner @ ErrorCode::Unauthorized
+    )]
+    pub user_portfolio: Box<Account<'info, UserPortfolio>>,
+
+    #[account(mut, address = order.mint @ ErrorCode::InvalidToken)]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"price_observations", mint.key().as_ref()],
+        bump
+    )]
+    pub price_observations: Box<Account<'info, PriceObservations>>,
+
+    /// CHECK: only receives the burned launch protection surplus
+    #[account(mut, address = incinerator::ID)]
+    pub incinerator: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+// Fills an order whose trigger the spot price has reached, through the curve as mint_token and
+// burn_token would for the owner, and pays the keeper KEEPER_REWARD_BPS of the lamports traded.
+// The order closes to the owner.
+pub fn execute_order(ctx: Context<ExecuteOrder>) -> Result<()> {
+    let accounts = ctx.accounts;
+    let order = &accounts.order;
+    require!(
+        accounts.order_token_account.is_some() == (order.side == TradeSide::Sell),
+        ErrorCode::InvalidOrder
+    );
+    let spot_price = accounts.bonding_curve.calculate_price(accounts.token_info.total_supply)?;
+    require!(order.is_triggered(spot_price), ErrorCode::OrderNotTriggered);
+
+    let owner = accounts.owner.to_account_info();
+    let order_info = order.to_account_info();
+    let id = order.id.to_le_bytes();
+    let order_seeds: &[&[&[u8]]] = &[&[b"order", order.owner.as_ref(), order.mint.as_ref(), &id, &[order.bump]]];
+    let (side, amount, limit) = (order.side, order.amount, order.limit);
+
+    let (amount_out, keeper_reward) = match (side, &accounts.order_token_account) {
+        (TradeSide::Buy, _) => {
+            let keeper_reward = fee_for(amount, Order::KEEPER_REWARD_BPS);
+            let tokens_minted = buy(
+                Purchase {
+                    buyer: &accounts.keeper,
+                    global_state: &accounts.global_state,
+                    token_info: &mut accounts.token_info,
+                    token_info_bump: ctx.bumps.token_info,
+                    bonding_curve: &accounts.bonding_curve,
+                    liquidity_pool: &mut accounts.liquidity_pool,
+                    user_portfolio: &mut accounts.user_portfolio,
+                    mint: &accounts.mint,
+                    token_account: &accounts.user_token_account,
+                    price_observations: &mut accounts.price_observations,
+                    incinerator: Some(&accounts.incinerator),
+                    token_program: &accounts.token_program,
+                    system_program: &accounts.system_program,
+                },
+                owner.key(),
+                amount - keeper_reward,
+                &[],
+            )?;
+            require!(tokens_minted >= limit, ErrorCode::SlippageExceeded);
+
+            // Repay the keeper from the escrow once no CPI follows
+            pay_out(&order_info, &accounts.keeper, amount)?;
+            (tokens_minted, keeper_reward)
+        }
+        (TradeSide::Sell, Some(order_token_account)) => {
+            // The portfolio counted the escrowed tokens all along, and the sale takes them off it
+            let sol_out = sell(
+                Sale {
+                    seller: &owner,
+                    authority: &order_info,
+                    authority_seeds: order_seeds,
+                    global_state: &accounts.global_state,
+                    token_info: &mut accounts.token_info,
+                    bonding_curve: &accounts.bonding_curve,
+                    liquidity_pool: &mut accounts.liquidity_pool,
+                    user_portfolio: &mut accounts.user_portfolio,
+                    mint: &accounts.mint,
+                    token_account: order_token_account,
+                    price_observations: &mut accounts.price_observations,
+                    token_program: &accounts.token_program,
+                },
+                amount,
+                false,
+            )?;
+            let keeper_reward = fee_for(sol_out, Order::KEEPER_REWARD_BPS);
+            let amount_to_owner = sol_out - keeper_reward;
+            require!(amount_to_owner >= limit, ErrorCode::SlippageExceeded);
+
+            // The emptied escrow refunds its rent to the owner
+            let cpi_accounts = token::CloseAccount {
+                account: order_token_account.to_account_info(),
+                destination: owner.clone(),
+                authority: order_info.clone(),
+            };
+            token::close_account(CpiContext::new_with_signer(accounts.token_program.to_account_info(), cpi_accounts, order_seeds))?;
+
+            pay_out(&accounts.liquidity_pool.to_account_info(), &accounts.keeper, keeper_reward)?;
+            settle_sale(
+                &owner,
+                &accounts.liquidity_pool,
+                &accounts.bonding_curve,
+                accounts.token_info.total_supply,
+                amount_to_owner,
+            )?;
+            (amount_to_owner, keeper_reward)
+        }
+        (TradeSide::Sell, None) => return Err(ErrorCode::InvalidOrder.into()),
+    };
+
+    emit!(OrderExecuted {
+        order: accounts.order.key(),
+        token: accounts.mint.key(),
+        owner: owner.key(),
+        keeper: accounts.keeper.key(),
+        amount_out,
+        keeper_reward,
+    });
+
+    Ok(())
+}
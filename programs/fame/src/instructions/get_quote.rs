@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::state::{TokenInfo, BondingCurve, LiquidityPool, GlobalState, fee_for};
+use crate::errors::ErrorCode;
+use crate::instructions::mint_token::calculate_tokens_to_mint;
+use crate::instructions::burn_token::calculate_sol_to_return;
+
+#[derive(Accounts)]
+pub struct GetQuote<'info> {
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        constraint = token_info.mint == mint.key() @ ErrorCode::InvalidToken
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+
+    #[account(
+        constraint = bonding_curve.token == mint.key() @ ErrorCode::InvalidToken
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(
+        constraint = liquidity_pool.token == mint.key() @ ErrorCode::InvalidToken
+    )]
+    pub liquidity_pool: Account<'info, LiquidityPool>,
+
+    pub mint: Account<'info, Mint>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct Quote {
+    // Spot price at the current supply, in lamports per base token unit
+    pub spot_price: u64,
+    // Tokens minted by `mint_token(amount_sol)` and the fee kept from `amount_sol`, including any
+    // launch protection surplus. None if the buy would fail
+    pub buy: Option<SideQuote>,
+    // Lamports paid out by `burn_token(amount_tokens)` and the fee kept from them. None if the
+    // sell would fail
+    pub sell: Option<SideQuote>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SideQuote {
+    pub amount_out: u64,
+    pub fee: u64,
+}
+
+pub fn get_quote(ctx: Context<GetQuote>, amount_sol: u64, amount_tokens: u64) -> Result<Quote> {
+    let token_info = &ctx.accounts.token_info;
+    let clock = Clock::get()?;
+    quote(
+        &ctx.accounts.global_state,
+        token_info.buy_fee_bps(ctx.accounts.global_state.buy_fee_bps, clock.slot, clock.unix_timestamp),
+        &ctx.accounts.bonding_curve,
+        token_info.total_supply,
+        ctx.accounts.liquidity_pool.balance,
+        amount_sol,
+        amount_tokens,
+    )
+}
+
+// Quotes a buy of `amount_sol` at a buy fee of `buy_fee_bps` (see TokenInfo::buy_fee_bps) and a
+// sell of `amount_tokens` against the curve state, exactly as mint_token and burn_token would
+// execute them. Each side is quoted on its own, so that one failing leaves the other. Shared
+// with off-chain clients.
+pub fn quote(
+    global_state: &GlobalState,
+    buy_fee_bps: u16,
+    bonding_curve: &BondingCurve,
+    supply: u64,
+    pool_balance: u64,
+    amount_sol: u64,
+    amount_tokens: u64,
+) -> Result<Quote> {
+    Ok(Quote {
+        spot_price: bonding_curve.calculate_price(supply)?,
+        buy: quote_buy(buy_fee_bps, bonding_curve, supply, amount_sol).ok(),
+        sell: quote_sell(global_state, bonding_curve, supply, pool_balance, amount_tokens).ok(),
+    })
+}
+
+// Mirrors mint_token
+pub fn quote_buy(buy_fee_bps: u16, bonding_curve: &BondingCurve, supply: u64, amount_sol: u64) -> Result<SideQuote> {
+    let fee = fee_for(amount_sol, buy_fee_bps);
+    let amount_out = calculate_tokens_to_mint(bonding_curve, supply, amount_sol - fee)?;
+    require!(
+        supply.checked_add(amount_out).is_some_and(|supply| supply <= bonding_curve.max_supply),
+        ErrorCode::MaxSupplyExceeded
+    );
+    Ok(SideQuote { amount_out, fee })
+}
+
+// Mirrors burn_token
+pub fn quote_sell(
+    global_state: &GlobalState,
+    bonding_curve: &BondingCurve,
+    supply: u64,
+    pool_balance: u64,
+    amount_tokens: u64,
+) -> Result<SideQuote> {
+    let sol_to_return = calculate_sol_to_return(bonding_curve, supply, amount_tokens)?;
+    require!(pool_balance >= sol_to_return, ErrorCode::InsufficientLiquidity);
+    let fee = global_state.sell_fee(sol_to_return);
+    Ok(SideQuote {
+        amount_out: sol_to_return - fee,
+        fee,
+    })
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::state::{TokenInfo, BondingCurve, PriceObservations};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct GetTwap<'info> {
+    #[account(
+        constraint = token_info.mint == mint.key() @ ErrorCode::InvalidToken
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+
+    #[account(
+        constraint = bonding_curve.token == mint.key() @ ErrorCode::InvalidToken
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(
+        seeds = [b"price_observations", mint.key().as_ref()],
+        bump
+    )]
+    pub price_observations: Box<Account<'info, PriceObservations>>,
+
+    pub mint: Account<'info, Mint>,
+}
+
+pub fn get_twap(ctx: Context<GetTwap>, window_seconds: u32) -> Result<u64> {
+    let spot_price = ctx
+        .accounts
+        .bonding_curve
+        .calculate_price(ctx.accounts.token_info.total_supply)?;
+
+    ctx.accounts
+        .price_observations
+        .twap(Clock::get()?.unix_timestamp, spot_price, window_seconds)
+}
@@ -9,7 +9,7 @@ pub struct Initialize<'info> {
     #[account(
         init,
         payer = admin,
-        space = 8 + 32, // discriminator + pubkey
+        space = GlobalState::LEN,
         seeds = [b"program_state"],
         bump
     )]
@@ -21,6 +21,12 @@ pub struct Initialize<'info> {
 pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
     let global_state = &mut ctx.accounts.global_state;
     global_state.admin = ctx.accounts.admin.key();
+    global_state.paused = false;
+    global_state.buy_fee_bps = GlobalState::DEFAULT_FEE_BPS;
+    global_state.sell_fee_bps = GlobalState::DEFAULT_FEE_BPS;
+    global_state.max_buy_sol = 0;
+    global_state.max_sell_tokens = 0;
+    global_state.max_slot_volume = 0;
 
     msg!("Contract initialized. Admin: {:?}", global_state.admin);
     Ok(())
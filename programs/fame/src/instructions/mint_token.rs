@@ -1,15 +1,31 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::incinerator;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
-use crate::state::{TokenInfo, BondingCurve, LiquidityPool, UserPortfolio};
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::associated_token::AssociatedToken;
+use crate::state::{TokenInfo, BondingCurve, LiquidityPool, UserPortfolio, PriceObservations, GlobalState, fee_for};
 use crate::errors::ErrorCode;
+use crate::instructions::verify_pool::require_solvent;
+use crate::events::{TokenMinted, PriceUpdate, FeeCollected, LaunchFeeCollected};
 
 #[derive(Accounts)]
 pub struct MintToken<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
+    /// CHECK: any wallet, only its key is used; the user themselves unless buying for someone else
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
     #[account(
         mut,
+        seeds = [b"token_info", mint.key().as_ref()],
+        bump,
         constraint = token_info.mint == mint.key() @ ErrorCode::InvalidToken
     )]
     pub token_info: Account<'info, TokenInfo>,
@@ -27,9 +43,11 @@ pub struct MintToken<'info> {
     pub liquidity_pool: Account<'info, LiquidityPool>,
 
     #[account(
-        mut,
-        constraint = user_portfolio.user == user.key() @ ErrorCode::Unauthorized,
-        constraint = user_portfolio.token == mint.key() @ ErrorCode::InvalidToken
+        init_if_needed,
+        payer = user,
+        space = UserPortfolio::LEN,
+        seeds = [b"user_portfolio", recipient.key().as_ref(), mint.key().as_ref()],
+        bump
     )]
     pub user_portfolio: Account<'info, UserPortfolio>,
 
@@ -37,90 +55,335 @@ pub struct MintToken<'info> {
     pub mint: Account<'info, Mint>,
 
     #[account(
-        mut,
+        init_if_needed,
+        payer = user,
         associated_token::mint = mint,
-        associated_token::authority = user
+        associated_token::authority = recipient
     )]
     pub user_token_account: Account<'info, TokenAccount>,
 
+    #[account(
+        mut,
+        seeds = [b"price_observations", mint.key().as_ref()],
+        bump
+    )]
+    pub price_observations: Box<Account<'info, PriceObservations>>,
+
+    /// CHECK: only receives the burned launch protection surplus
+    #[account(mut, address = incinerator::ID)]
+    pub incinerator: UncheckedAccount<'info>,
+
+    // The user's wrapped SOL account, to pay from instead of their lamports
+    #[account(
+        mut,
+        constraint = wsol_account.mint == native_mint::ID @ ErrorCode::InvalidWsolAccount,
+        constraint = wsol_account.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub wsol_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Unwraps the payment, and is closed again within the instruction
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"wsol_unwrap", user.key().as_ref()],
+        bump,
+        token::mint = native_mint,
+        token::authority = token_info
+    )]
+    pub wsol_unwrap: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(address = native_mint::ID @ ErrorCode::InvalidWsolAccount)]
+    pub native_mint: Option<Box<Account<'info, Mint>>>,
+
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn mint_token(ctx: Context<MintToken>, amount_sol: u64) -> Result<()> {
-    let token_info = &mut ctx.accounts.token_info;
-    let bonding_curve = &ctx.accounts.bonding_curve;
-    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
-    let user_portfolio = &mut ctx.accounts.user_portfolio;
-    let user = &ctx.accounts.user;
+// The user pays and the recipient is credited, in its token account and portfolio. `proof` places
+// the recipient on the presale allowlist and is ignored once public trading is open
+pub fn mint_token(ctx: Context<MintToken>, amount_sol: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+    let recipient = ctx.accounts.recipient.key();
+
+    // Paying from wrapped SOL takes all three wrapped SOL accounts
+    let accounts = &ctx.accounts;
+    match (&accounts.wsol_account, &accounts.wsol_unwrap, &accounts.native_mint) {
+        (Some(wsol_account), Some(wsol_unwrap), Some(_)) => unwrap_wsol(
+            token::Transfer {
+                from: wsol_account.to_account_info(),
+                to: wsol_unwrap.to_account_info(),
+                authority: accounts.user.to_account_info(),
+            },
+            &[],
+            accounts.user.to_account_info(),
+            &accounts.token_info,
+            ctx.bumps.token_info,
+            &accounts.token_program,
+            amount_sol,
+        )?,
+        (None, None, None) => {}
+        _ => return Err(ErrorCode::InvalidWsolAccount.into()),
+    }
+
+    buy(
+        Purchase {
+            buyer: &ctx.accounts.user,
+            global_state: &ctx.accounts.global_state,
+            token_info: &mut ctx.accounts.token_info,
+            token_info_bump: ctx.bumps.token_info,
+            bonding_curve: &ctx.accounts.bonding_curve,
+            liquidity_pool: &mut ctx.accounts.liquidity_pool,
+            user_portfolio: &mut ctx.accounts.user_portfolio,
+            mint: &ctx.accounts.mint,
+            token_account: &ctx.accounts.user_token_account,
+            price_observations: &mut ctx.accounts.price_observations,
+            incinerator: Some(&ctx.accounts.incinerator),
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+        },
+        recipient,
+        amount_sol,
+        &proof,
+    )?;
+
+    Ok(())
+}
 
-    // Calculate the number of tokens to mint based on the bonding curve
-    let tokens_to_mint = calculate_tokens_to_mint(bonding_curve, token_info.total_supply, amount_sol)?;
+// Moves `amount` out of a wrapped SOL account into the lamports of `destination`: `transfer` takes
+// the tokens to the fresh unwrap account, signed by the owner or a delegate PDA with
+// `authority_seeds`, and closing it refunds its rent to `destination` along with them
+pub fn unwrap_wsol<'info>(
+    transfer: token::Transfer<'info>,
+    authority_seeds: &[&[&[u8]]],
+    destination: AccountInfo<'info>,
+    token_info: &Account<'info, TokenInfo>,
+    token_info_bump: u8,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let wsol_unwrap = transfer.to.clone();
+    token::transfer(CpiContext::new_with_signer(token_program.to_account_info(), transfer, authority_seeds), amount)?;
 
-    // Calculate fee (1% of the transaction volume)
-    let fee = amount_sol / 100;
-    let amount_to_pool = amount_sol - fee;
+    let mint_key = token_info.mint;
+    let signer_seeds: &[&[&[u8]]] = &[&[b"token_info", mint_key.as_ref(), &[token_info_bump]]];
+    let cpi_accounts = token::CloseAccount {
+        account: wsol_unwrap,
+        destination,
+        authority: token_info.to_account_info(),
+    };
+    token::close_account(CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds))
+}
+
+// Public buy for `recipient` under the token's launch rules, returning the tokens minted
+pub fn buy(accounts: Purchase, recipient: Pubkey, amount_sol: u64, proof: &[[u8; 32]]) -> Result<u64> {
+    let global_state = accounts.global_state;
+
+    require!(!global_state.paused, ErrorCode::TradingPaused);
+    require!(amount_sol > 0, ErrorCode::InvalidMintAmount);
+    let clock = Clock::get()?;
+    require!(clock.unix_timestamp >= accounts.token_info.launch_at, ErrorCode::NotLaunched);
+
+    // Until the presale ends only allowlisted wallets buy, at the protocol fee
+    let token_info = &mut *accounts.token_info;
+    let presale = token_info.presale.filter(|presale| clock.unix_timestamp < presale.ends_at);
+    if let Some(presale) = &presale {
+        require!(presale.is_allowlisted(&recipient, proof), ErrorCode::NotAllowlisted);
+    }
+
+    // A scheduled launch opens the protection window with its first public buy
+    if token_info.launch_slot == 0 && presale.is_none() {
+        token_info.launch_slot = clock.slot;
+    }
+    let fee_bps = token_info.buy_fee_bps(global_state.buy_fee_bps, clock.slot, clock.unix_timestamp);
+    // Early on, no wallet may buy past the holding limit
+    let holding_cap = token_info.holding_limit.and_then(|limit| {
+        limit.cap_at(accounts.bonding_curve.max_supply, token_info.total_supply, clock.unix_timestamp)
+    });
+
+    // First buy of this token for the recipient
+    let user_portfolio = &mut *accounts.user_portfolio;
+    if user_portfolio.user == Pubkey::default() {
+        user_portfolio.user = recipient;
+        user_portfolio.token = accounts.mint.key();
+    }
+    let balance_before = user_portfolio.balance;
+
+    let tokens_minted = purchase(accounts, amount_sol, fee_bps)?;
+
+    let balance = balance_before + tokens_minted;
+    if let Some(cap) = holding_cap {
+        require!(balance <= cap, ErrorCode::HoldingLimitExceeded);
+    }
+    if let Some(presale) = presale {
+        require!(balance <= presale.max_per_wallet, ErrorCode::PresaleCapExceeded);
+    }
+
+    Ok(tokens_minted)
+}
+
+// Accounts a curve purchase touches, shared by the instructions that buy from the curve
+pub struct Purchase<'a, 'info> {
+    pub buyer: &'a Signer<'info>,
+    pub global_state: &'a GlobalState,
+    pub token_info: &'a mut Account<'info, TokenInfo>,
+    pub token_info_bump: u8,
+    pub bonding_curve: &'a BondingCurve,
+    pub liquidity_pool: &'a mut Account<'info, LiquidityPool>,
+    pub user_portfolio: &'a mut Account<'info, UserPortfolio>,
+    pub mint: &'a Account<'info, Mint>,
+    pub token_account: &'a Account<'info, TokenAccount>,
+    pub price_observations: &'a mut Account<'info, PriceObservations>,
+    // Needed when a launch protection surplus may be burned
+    pub incinerator: Option<&'a UncheckedAccount<'info>>,
+    pub token_program: &'a Program<'info, Token>,
+    pub system_program: &'a Program<'info, System>,
+}
+
+// Buys from the curve with `amount_sol` paid by the buyer at a buy fee of `fee_bps`, and returns
+// the tokens minted. The fee above the protocol rate is the launch protection surplus.
+pub fn purchase(accounts: Purchase, amount_sol: u64, fee_bps: u16) -> Result<u64> {
+    let Purchase {
+        buyer,
+        global_state,
+        token_info,
+        token_info_bump,
+        bonding_curve,
+        liquidity_pool,
+        user_portfolio,
+        mint,
+        token_account,
+        price_observations,
+        incinerator,
+        token_program,
+        system_program,
+    } = accounts;
+
+    require!(
+        global_state.max_buy_sol == 0 || amount_sol <= global_state.max_buy_sol,
+        ErrorCode::BuyLimitExceeded
+    );
+    let clock = Clock::get()?;
+    liquidity_pool.record_volume(clock.slot, amount_sol, global_state.max_slot_volume)?;
+
+    // Record the price that held until this trade
+    let spot_price = bonding_curve.calculate_price(token_info.total_supply)?;
+    price_observations.record(clock.unix_timestamp, spot_price)?;
+
+    // Calculate fee (buy fee share of the transaction volume)
+    let fee = global_state.buy_fee(amount_sol);
+    let surplus = fee_for(amount_sol, fee_bps).saturating_sub(fee);
+    let burn_surplus = token_info.launch_protection.is_some_and(|protection| protection.burn_surplus);
+    let amount_to_pool = amount_sol - fee - surplus;
+
+    // Calculate the number of tokens to mint for what actually reaches the curve
+    let tokens_to_mint = calculate_tokens_to_mint(bonding_curve, token_info.total_supply, amount_to_pool)?;
 
     // Update liquidity pool
     liquidity_pool.balance = liquidity_pool.balance.checked_add(amount_to_pool)
         .ok_or(ErrorCode::ArithmeticOverflow)?;
-    liquidity_pool.accumulated_fees = liquidity_pool.accumulated_fees.checked_add(fee)
+    // The creator withdraws the surplus together with the fees, unless it is burned
+    let pool_fee = if burn_surplus { fee } else { fee + surplus };
+    liquidity_pool.accumulated_fees = liquidity_pool.accumulated_fees.checked_add(pool_fee)
         .ok_or(ErrorCode::ArithmeticOverflow)?;
 
     // Update token info
     token_info.total_supply = token_info.total_supply.checked_add(tokens_to_mint)
         .ok_or(ErrorCode::ArithmeticOverflow)?;
+    require!(token_info.total_supply <= bonding_curve.max_supply, ErrorCode::MaxSupplyExceeded);
 
     // Update user portfolio
     user_portfolio.balance = user_portfolio.balance.checked_add(tokens_to_mint)
         .ok_or(ErrorCode::ArithmeticOverflow)?;
 
     // Transfer SOL from user to program
+    let burned = if burn_surplus { surplus } else { 0 };
     let cpi_context = CpiContext::new(
-        ctx.accounts.system_program.to_account_info(),
+        system_program.to_account_info(),
         anchor_lang::system_program::Transfer {
-            from: user.to_account_info(),
+            from: buyer.to_account_info(),
             to: liquidity_pool.to_account_info(),
         },
     );
-    anchor_lang::system_program::transfer(cpi_context, amount_sol)?;
+    anchor_lang::system_program::transfer(cpi_context, amount_sol - burned)?;
+
+    if burned > 0 {
+        let incinerator = incinerator.ok_or(ErrorCode::InvalidLaunchProtection)?;
+        let cpi_context = CpiContext::new(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: buyer.to_account_info(),
+                to: incinerator.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, burned)?;
+    }
 
-    // Mint tokens to user
+    // Mint tokens to user, signed by the token_info PDA that holds the mint authority
+    let mint_key = mint.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"token_info", mint_key.as_ref(), &[token_info_bump]]];
     let cpi_accounts = token::MintTo {
-        mint: ctx.accounts.mint.to_account_info(),
-        to: ctx.accounts.user_token_account.to_account_info(),
+        mint: mint.to_account_info(),
+        to: token_account.to_account_info(),
         authority: token_info.to_account_info(),
     };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
     token::mint_to(cpi_ctx, tokens_to_mint)?;
 
-    Ok(())
+    // The pool must still be able to buy back the supply it backs
+    require_solvent(bonding_curve, token_info.total_supply, liquidity_pool)?;
+
+    // The fees precede the trade, so that indexers can price it without them
+    emit!(FeeCollected {
+        token: mint_key,
+        amount: fee,
+    });
+
+    if surplus > 0 {
+        emit!(LaunchFeeCollected {
+            token: mint_key,
+            user: buyer.key(),
+            amount: surplus,
+            burned: burn_surplus,
+        });
+    }
+
+    emit!(TokenMinted {
+        token: mint_key,
+        user: user_portfolio.user,
+        amount: tokens_to_mint,
+        price: amount_sol,
+        payer: buyer.key(),
+    });
+
+    emit!(PriceUpdate {
+        token: mint_key,
+        new_price: bonding_curve.calculate_price(token_info.total_supply)?,
+    });
+
+    Ok(tokens_to_mint)
 }
 
-fn calculate_tokens_to_mint(bonding_curve: &BondingCurve, current_supply: u64, amount_sol: u64) -> Result<u64> {
+pub fn calculate_tokens_to_mint(bonding_curve: &BondingCurve, current_supply: u64, amount_sol: u64) -> Result<u64> {
     // Convert parameters to f64 for precise calculations
     let p0 = bonding_curve.initial_price as f64 / 1e9; // Convert to SOL (assuming 9 decimal places)
     let k = bonding_curve.slope as f64 / 1e6; // Assuming slope is stored as an integer representation of 0.0000921
     let current_supply = current_supply as f64;
     let amount_sol = amount_sol as f64 / 1e9; // Convert to SOL
 
-    // Calculate the integral of the bonding curve function
-    // The integral of P(n) = P₀ * e^(k * n) is (P₀ / k) * (e^(k * n) - 1)
-    let integral = |n: f64| -> f64 {
-        (p0 / k) * ((k * n).exp() - 1.0)
-    };
-
-    // Calculate the number of tokens to mint
-    let current_integral = integral(current_supply);
-    let target_integral = current_integral + amount_sol;
+    // A flat or free curve has no inverse
+    if p0 == 0.0 || k == 0.0 {
+        return Err(ErrorCode::InvalidBondingCurveParameters.into());
+    }
 
-    // Solve for n: target_integral = (P₀ / k) * (e^(k * n) - 1)
-    let new_supply = (1.0 / k) * ((target_integral * k / p0 + 1.0).ln());
+    // The integral of P(n) = P₀ * e^(k * n) is I(n) = (P₀ / k) * (e^(k * n) - 1).
+    // Solving I(s + t) = I(s) + amount for t gives t = ln(1 + amount * k / (P₀ * e^(k * s))) / k,
+    // which avoids subtracting two large integrals from each other
+    let tokens_to_mint = (amount_sol * k / p0 * (-k * current_supply).exp()).ln_1p() / k;
 
-    // Calculate the difference to get the number of new tokens
-    let tokens_to_mint = (new_supply - current_supply).max(0.0);
+    if !tokens_to_mint.is_finite() || tokens_to_mint >= u64::MAX as f64 {
+        return Err(ErrorCode::ArithmeticOverflow.into());
+    }
 
     // Convert back to u64, rounding down
     Ok(tokens_to_mint.floor() as u64)
-}
\ No newline at end of file
+}
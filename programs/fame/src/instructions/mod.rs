@@ -3,9 +3,37 @@ pub mod mint_token;
 pub mod burn_token;
 pub mod withdrawal_fee;
 pub mod initialize;
+pub mod get_twap;
+pub mod get_quote;
+pub mod set_paused;
+pub mod set_fees;
+pub mod verify_pool;
+pub mod claim_vested;
+pub mod set_trade_limits;
+pub mod burn_all;
+pub mod batch_trade;
+pub mod swap;
+pub mod execute_intent;
+pub mod place_order;
+pub mod cancel_order;
+pub mod execute_order;
 
 pub use create_token::*;
 pub use mint_token::*;
 pub use burn_token::*;
 pub use withdrawal_fee::*;
-pub use initialize::*;
\ No newline at end of file
+pub use initialize::*;
+pub use get_twap::*;
+pub use get_quote::*;
+pub use set_paused::*;
+pub use set_fees::*;
+pub use verify_pool::*;
+pub use claim_vested::*;
+pub use set_trade_limits::*;
+pub use burn_all::*;
+pub use batch_trade::*;
+pub use swap::*;
+pub use execute_intent::*;
+pub use place_order::*;
+pub use cancel_order::*;
+pub use execute_order::*;
\ No newline at end of file
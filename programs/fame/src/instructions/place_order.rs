@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::state::{TokenInfo, UserPortfolio, Order};
+use crate::errors::ErrorCode;
+use crate::events::OrderPlaced;
+use crate::instructions::batch_trade::TradeSide;
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct PlaceOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"token_info", mint.key().as_ref()],
+        bump,
+        constraint = token_info.mint == mint.key() @ ErrorCode::InvalidToken
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = Order::LEN,
+        seeds = [b"order", owner.key().as_ref(), mint.key().as_ref(), &id.to_le_bytes()],
+        bump
+    )]
+    pub order: Account<'info, Order>,
+
+    // Holds the tokens a sell order escrows
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = order
+    )]
+    pub order_token_account: Option<Account<'info, TokenAccount>>,
+
+    // Created for a buy order up front, so that filling it creates nothing
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = UserPortfolio::LEN,
+        seeds = [b"user_portfolio", owner.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub user_portfolio: Account<'info, UserPortfolio>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = owner
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+// Places limit order `id` of the owner: a buy of `amount` lamports once the spot price falls to
+// `trigger_price`, or a sell of `amount` tokens once it rises to it. The escrow pays the keeper
+// reward, and `limit` bounds what the owner gets after it.
+pub fn place_order(
+    ctx: Context<PlaceOrder>,
+    id: u64,
+    side: TradeSide,
+    amount: u64,
+    trigger_price: u64,
+    limit: u64,
+) -> Result<()> {
+    require!(amount > 0 && trigger_price > 0, ErrorCode::InvalidOrder);
+    // Only a sell escrows tokens
+    require!(
+        ctx.accounts.order_token_account.is_some() == (side == TradeSide::Sell),
+        ErrorCode::InvalidOrder
+    );
+
+    let owner = &ctx.accounts.owner;
+    let mint = ctx.accounts.mint.key();
+    let user_portfolio = &mut ctx.accounts.user_portfolio;
+    if user_portfolio.user == Pubkey::default() {
+        user_portfolio.user = owner.key();
+        user_portfolio.token = mint;
+    }
+    // The portfolio keeps counting the escrowed tokens until the order sells them, so it must
+    // hold them in the first place
+    if side == TradeSide::Sell {
+        require!(user_portfolio.balance >= amount, ErrorCode::InsufficientBalance);
+    }
+
+    let order = &mut ctx.accounts.order;
+    order.owner = owner.key();
+    order.mint = mint;
+    order.id = id;
+    order.side = side;
+    order.amount = amount;
+    order.trigger_price = trigger_price;
+    order.limit = limit;
+    order.bump = ctx.bumps.order;
+
+    match &ctx.accounts.order_token_account {
+        Some(order_token_account) => {
+            let cpi_accounts = token::Transfer {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                to: order_token_account.to_account_info(),
+                authority: owner.to_account_info(),
+            };
+            token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), amount)?;
+        }
+        None => {
+            let cpi_context = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: owner.to_account_info(),
+                    to: order.to_account_info(),
+                },
+            );
+            anchor_lang::system_program::transfer(cpi_context, amount)?;
+        }
+    }
+
+    emit!(OrderPlaced {
+        order: order.key(),
+        token: mint,
+        owner: owner.key(),
+        buy: side == TradeSide::Buy,
+        amount,
+        trigger_price,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use crate::errors::ErrorCode;
+use crate::state::GlobalState;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetFees<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump,
+        constraint = global_state.admin == admin.key() @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn set_fees(ctx: Context<SetFees>, buy_fee_bps: u16, sell_fee_bps: u16) -> Result<()> {
+    require!(buy_fee_bps <= GlobalState::MAX_FEE_BPS, ErrorCode::InvalidFee);
+    require!(sell_fee_bps <= GlobalState::MAX_FEE_BPS, ErrorCode::InvalidFee);
+
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.buy_fee_bps = buy_fee_bps;
+    global_state.sell_fee_bps = sell_fee_bps;
+
+    msg!("Fees updated. Buy: {} bps, sell: {} bps", buy_fee_bps, sell_fee_bps);
+    Ok(())
+}
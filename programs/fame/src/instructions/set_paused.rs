@@ -0,0 +1,23 @@
+use crate::errors::ErrorCode;
+use crate::state::GlobalState;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump,
+        constraint = global_state.admin == admin.key() @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    ctx.accounts.global_state.paused = paused;
+
+    msg!("Trading paused: {}", paused);
+    Ok(())
+}
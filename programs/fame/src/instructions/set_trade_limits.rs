@@ -0,0 +1,37 @@
+use crate::errors::ErrorCode;
+use crate::state::GlobalState;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetTradeLimits<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"program_state"],
+        bump,
+        constraint = global_state.admin == admin.key() @ ErrorCode::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+// Limits of 0 lift the corresponding check
+pub fn set_trade_limits(
+    ctx: Context<SetTradeLimits>,
+    max_buy_sol: u64,
+    max_sell_tokens: u64,
+    max_slot_volume: u64,
+) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.max_buy_sol = max_buy_sol;
+    global_state.max_sell_tokens = max_sell_tokens;
+    global_state.max_slot_volume = max_slot_volume;
+
+    msg!(
+        "Trade limits updated. Buy: {} lamports, sell: {} tokens, slot volume: {} lamports",
+        max_buy_sol,
+        max_sell_tokens,
+        max_slot_volume
+    );
+    Ok(())
+}
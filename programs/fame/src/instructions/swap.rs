@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::incinerator;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::state::{TokenInfo, BondingCurve, LiquidityPool, UserPortfolio, PriceObservations, GlobalState};
+use crate::errors::ErrorCode;
+use crate::instructions::mint_token::{buy, Purchase};
+use crate::instructions::burn_token::{sell, settle_sale, Sale};
+use crate::instructions::batch_trade::TradeSide;
+
+// Uniform entrypoint for aggregators. Both sides take the same accounts, in this order:
+//  0. user                      signer, writable
+//  1. global_state              ["program_state"]
+//  2. token_info                ["token_info", mint], writable
+//  3. bonding_curve             ["bonding_curve", mint]
+//  4. liquidity_pool            ["liquidity_pool", mint], writable
+//  5. user_portfolio            ["user_portfolio", user, mint], writable, created on a first buy
+//  6. mint                      writable
+//  7. user_token_account        associated token account of the user, writable, created on a first buy
+//  8. price_observations        ["price_observations", mint], writable
+//  9. incinerator               writable
+// 10. token_program
+// 11. associated_token_program
+// 12. system_program
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"program_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"token_info", mint.key().as_ref()],
+        bump,
+        constraint = token_info.mint == mint.key() @ ErrorCode::InvalidToken
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+
+    #[account(
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump,
+        constraint = bonding_curve.token == mint.key() @ ErrorCode::InvalidToken
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_pool", mint.key().as_ref()],
+        bump,
+        constraint = liquidity_pool.token == mint.key() @ ErrorCode::InvalidToken
+    )]
+    pub liquidity_pool: Account<'info, LiquidityPool>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserPortfolio::LEN,
+        seeds = [b"user_portfolio", user.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub user_portfolio: Account<'info, UserPortfolio>,
+
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint,
+        associated_token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"price_observations", mint.key().as_ref()],
+        bump
+    )]
+    pub price_observations: Box<Account<'info, PriceObservations>>,
+
+    /// CHECK: only receives the burned launch protection surplus
+    #[account(mut, address = incinerator::ID)]
+    pub incinerator: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+// A buy spends `amount_in` lamports for at least `min_amount_out` tokens, a sell burns
+// `amount_in` tokens for at least `min_amount_out` lamports after the fee. Returns the amount out.
+pub fn swap(ctx: Context<Swap>, amount_in: u64, min_amount_out: u64, side: TradeSide) -> Result<u64> {
+    let accounts = ctx.accounts;
+    let amount_out = match side {
+        // Allowlisted presale buys go through mint_token, which takes the proof
+        TradeSide::Buy => buy(
+            Purchase {
+                buyer: &accounts.user,
+                global_state: &accounts.global_state,
+                token_info: &mut accounts.token_info,
+                token_info_bump: ctx.bumps.token_info,
+                bonding_curve: &accounts.bonding_curve,
+                liquidity_pool: &mut accounts.liquidity_pool,
+                user_portfolio: &mut accounts.user_portfolio,
+                mint: &accounts.mint,
+                token_account: &accounts.user_token_account,
+                price_observations: &mut accounts.price_observations,
+                incinerator: Some(&accounts.incinerator),
+                token_program: &accounts.token_program,
+                system_program: &accounts.system_program,
+            },
+            accounts.user.key(),
+            amount_in,
+            &[],
+        )?,
+        TradeSide::Sell => {
+            require_keys_eq!(accounts.user_portfolio.user, accounts.user.key(), ErrorCode::Unauthorized);
+            let amount_to_user = sell(
+                Sale {
+                    seller: &accounts.user,
+                    authority: &accounts.user,
+                    authority_seeds: &[],
+                    global_state: &accounts.global_state,
+                    token_info: &mut accounts.token_info,
+                    bonding_curve: &accounts.bonding_curve,
+                    liquidity_pool: &mut accounts.liquidity_pool,
+                    user_portfolio: &mut accounts.user_portfolio,
+                    mint: &accounts.mint,
+                    token_account: &accounts.user_token_account,
+                    price_observations: &mut accounts.price_observations,
+                    token_program: &accounts.token_program,
+                },
+                amount_in,
+                false,
+            )?;
+            settle_sale(
+                &accounts.user,
+                &accounts.liquidity_pool,
+                &accounts.bonding_curve,
+                accounts.token_info.total_supply,
+                amount_to_user,
+            )?;
+            amount_to_user
+        }
+    };
+    require!(amount_out >= min_amount_out, ErrorCode::SlippageExceeded);
+
+    Ok(amount_out)
+}
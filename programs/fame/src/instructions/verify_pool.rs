@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::state::{TokenInfo, BondingCurve, LiquidityPool};
+use crate::errors::ErrorCode;
+use crate::events::PoolVerified;
+use crate::instructions::burn_token::calculate_sol_to_return;
+
+#[derive(Accounts)]
+pub struct VerifyPool<'info> {
+    #[account(
+        constraint = token_info.mint == mint.key() @ ErrorCode::InvalidToken
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+
+    #[account(
+        constraint = bonding_curve.token == mint.key() @ ErrorCode::InvalidToken
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(
+        constraint = liquidity_pool.token == mint.key() @ ErrorCode::InvalidToken
+    )]
+    pub liquidity_pool: Account<'info, LiquidityPool>,
+
+    pub mint: Account<'info, Mint>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PoolReport {
+    pub total_supply: u64,
+    // Lamports burning the whole supply would pay out, before fees
+    pub required_reserves: u64,
+    // Recorded reserves and fees of the liquidity pool
+    pub balance: u64,
+    pub accumulated_fees: u64,
+    // Lamports actually held by the liquidity pool account, and the part of them kept for rent
+    pub lamports: u64,
+    pub rent_exempt_minimum: u64,
+    // Recorded reserves cover the supply and the account holds everything it has recorded
+    pub solvent: bool,
+}
+
+pub fn verify_pool(ctx: Context<VerifyPool>) -> Result<PoolReport> {
+    let liquidity_pool = ctx.accounts.liquidity_pool.to_account_info();
+    let report = pool_report(
+        &ctx.accounts.bonding_curve,
+        ctx.accounts.token_info.total_supply,
+        &ctx.accounts.liquidity_pool,
+        liquidity_pool.lamports(),
+        Rent::get()?.minimum_balance(liquidity_pool.data_len()),
+    )?;
+
+    emit!(PoolVerified {
+        token: ctx.accounts.mint.key(),
+        total_supply: report.total_supply,
+        required_reserves: report.required_reserves,
+        balance: report.balance,
+        accumulated_fees: report.accumulated_fees,
+        lamports: report.lamports,
+        solvent: report.solvent,
+    });
+
+    Ok(report)
+}
+
+// Checks the liquidity pool of a token against its curve. Shared with mint_token and burn_token,
+// which refuse to leave a pool insolvent, and with off-chain clients.
+pub fn pool_report(
+    bonding_curve: &BondingCurve,
+    total_supply: u64,
+    liquidity_pool: &LiquidityPool,
+    lamports: u64,
+    rent_exempt_minimum: u64,
+) -> Result<PoolReport> {
+    // Every token must be backed, the creator allocation by what the creator paid for it
+    let required_reserves = calculate_sol_to_return(bonding_curve, total_supply, total_supply)?;
+    let recorded = liquidity_pool.balance
+        .checked_add(liquidity_pool.accumulated_fees)
+        .and_then(|recorded| recorded.checked_add(rent_exempt_minimum))
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    Ok(PoolReport {
+        total_supply,
+        required_reserves,
+        balance: liquidity_pool.balance,
+        accumulated_fees: liquidity_pool.accumulated_fees,
+        lamports,
+        rent_exempt_minimum,
+        solvent: required_reserves <= liquidity_pool.balance && recorded <= lamports,
+    })
+}
+
+// Fails the trade that would leave the pool of a token insolvent
+pub fn require_solvent(
+    bonding_curve: &BondingCurve,
+    total_supply: u64,
+    liquidity_pool: &Account<LiquidityPool>,
+) -> Result<()> {
+    let account = liquidity_pool.to_account_info();
+    let report = pool_report(
+        bonding_curve,
+        total_supply,
+        liquidity_pool,
+        account.lamports(),
+        Rent::get()?.minimum_balance(account.data_len()),
+    )?;
+    require!(report.solvent, ErrorCode::PoolInsolvent);
+    Ok(())
+}
@@ -2,7 +2,6 @@ use crate::errors::ErrorCode;
 use crate::events::FeeWithdrawn;
 use crate::state::LiquidityPool;
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
 
 #[derive(Accounts)]
 pub struct WithdrawFees<'info> {
@@ -37,15 +36,20 @@ pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
         .checked_sub(amount)
         .ok_or(ErrorCode::ArithmeticUnderflow)?;
 
-    // Transfer SOL from liquidity pool to fee receiver
-    let cpi_context = CpiContext::new(
-        ctx.accounts.system_program.to_account_info(),
-        system_program::Transfer {
-            from: liquidity_pool.to_account_info(),
-            to: ctx.accounts.fee_receiver.to_account_info(),
-        },
-    );
-    system_program::transfer(cpi_context, amount)?;
+    // Transfer SOL from liquidity pool to fee receiver. The pool carries data, so the
+    // system program cannot debit it; move the lamports directly instead.
+    let pool_info = liquidity_pool.to_account_info();
+    let receiver_info = ctx.accounts.fee_receiver.to_account_info();
+    let pool_lamports = pool_info
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ErrorCode::ArithmeticUnderflow)?;
+    let receiver_lamports = receiver_info
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    **pool_info.try_borrow_mut_lamports()? = pool_lamports;
+    **receiver_info.try_borrow_mut_lamports()? = receiver_lamports;
 
     // Emit an event for fee withdrawal
     emit!(FeeWithdrawn {
@@ -6,6 +6,7 @@ pub mod instructions;
 pub mod state;
 
 use instructions::*;
+use state::{LaunchProtection, HoldingLimit, Presale};
 
 declare_id!("3u8R4PRxhPTVNsGpGmzchZM1A3viTnyvZLTEzQjTE9q6");
 
@@ -17,24 +18,99 @@ pub mod fame {
         instructions::initialize(ctx)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_token(
         ctx: Context<CreateToken>,
         name: String,
         symbol: String,
         social_account_url: String,
+        initial_buy_sol: Option<u64>,
+        creator_allocation: Option<CreatorAllocation>,
+        launch_at: Option<i64>,
+        launch_protection: Option<LaunchProtection>,
+        holding_limit: Option<HoldingLimit>,
+        presale: Option<Presale>,
     ) -> Result<()> {
-        instructions::create_token(ctx, name, symbol, social_account_url)
+        instructions::create_token(ctx, name, symbol, social_account_url, initial_buy_sol, creator_allocation, launch_at, launch_protection, holding_limit, presale)
     }
 
-    pub fn mint_token(ctx: Context<MintToken>, amount_sol: u64) -> Result<()> {
-        instructions::mint_token(ctx, amount_sol)
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        instructions::claim_vested(ctx)
+    }
+
+    pub fn mint_token(ctx: Context<MintToken>, amount_sol: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        instructions::mint_token(ctx, amount_sol, proof)
     }
 
     pub fn burn_token(ctx: Context<BurnToken>, amount_tokens: u64) -> Result<()> {
         instructions::burn_token(ctx, amount_tokens)
     }
 
+    pub fn burn_all(ctx: Context<BurnToken>, close_accounts: bool) -> Result<()> {
+        instructions::burn_all(ctx, close_accounts)
+    }
+
+    pub fn batch_trade<'info>(ctx: Context<'_, '_, 'info, 'info, BatchTrade<'info>>, legs: Vec<TradeLeg>) -> Result<()> {
+        instructions::batch_trade(ctx, legs)
+    }
+
+    pub fn swap(ctx: Context<Swap>, amount_in: u64, min_amount_out: u64, side: TradeSide) -> Result<u64> {
+        instructions::swap(ctx, amount_in, min_amount_out, side)
+    }
+
+    pub fn execute_intent(ctx: Context<ExecuteIntent>, intent: TradeIntent) -> Result<()> {
+        instructions::execute_intent(ctx, intent)
+    }
+
+    pub fn place_order(
+        ctx: Context<PlaceOrder>,
+        id: u64,
+        side: TradeSide,
+        amount: u64,
+        trigger_price: u64,
+        limit: u64,
+    ) -> Result<()> {
+        instructions::place_order(ctx, id, side, amount, trigger_price, limit)
+    }
+
+    pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
+        instructions::cancel_order(ctx)
+    }
+
+    pub fn execute_order(ctx: Context<ExecuteOrder>) -> Result<()> {
+        instructions::execute_order(ctx)
+    }
+
     pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
         instructions::withdraw_fees(ctx, amount)
     }
+
+    pub fn get_twap(ctx: Context<GetTwap>, window_seconds: u32) -> Result<u64> {
+        instructions::get_twap(ctx, window_seconds)
+    }
+
+    pub fn get_quote(ctx: Context<GetQuote>, amount_sol: u64, amount_tokens: u64) -> Result<Quote> {
+        instructions::get_quote(ctx, amount_sol, amount_tokens)
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        instructions::set_paused(ctx, paused)
+    }
+
+    pub fn set_fees(ctx: Context<SetFees>, buy_fee_bps: u16, sell_fee_bps: u16) -> Result<()> {
+        instructions::set_fees(ctx, buy_fee_bps, sell_fee_bps)
+    }
+
+    pub fn set_trade_limits(
+        ctx: Context<SetTradeLimits>,
+        max_buy_sol: u64,
+        max_sell_tokens: u64,
+        max_slot_volume: u64,
+    ) -> Result<()> {
+        instructions::set_trade_limits(ctx, max_buy_sol, max_sell_tokens, max_slot_volume)
+    }
+
+    pub fn verify_pool(ctx: Context<VerifyPool>) -> Result<PoolReport> {
+        instructions::verify_pool(ctx)
+    }
 }
@@ -1,5 +1,7 @@
 use crate::errors::ErrorCode;
+use crate::instructions::TradeSide;
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
 
 #[account]
 pub struct TokenInfo {
@@ -9,6 +11,51 @@ pub struct TokenInfo {
     pub social_account_url: String,
     pub total_supply: u64,
     pub authority: Pubkey,
+    // Unix timestamp from which the curve accepts buys
+    pub launch_at: i64,
+    // Raised buy fee right after launch, and the slot its window opened at
+    pub launch_protection: Option<LaunchProtection>,
+    pub launch_slot: u64,
+    // Most tokens one wallet may buy into during the early curve phase
+    pub holding_limit: Option<HoldingLimit>,
+    // Allowlisted phase between launch and public trading
+    pub presale: Option<Presale>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LaunchProtection {
+    // Buy fee at launch, decaying linearly to the protocol buy fee over `duration_slots`
+    pub start_fee_bps: u16,
+    pub duration_slots: u64,
+    // Whether the fee above the protocol rate is burned instead of going to the creator
+    pub burn_surplus: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Presale {
+    // Root of the Merkle tree over the keccak hashes of the allowlisted wallets, with each pair
+    // of nodes hashed in sorted order
+    pub merkle_root: [u8; 32],
+    // Most tokens an allowlisted wallet may buy into during the presale
+    pub max_per_wallet: u64,
+    // Unix timestamp at which public trading opens
+    pub ends_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HoldingLimit {
+    pub cap: HoldingCap,
+    // The limit lifts once the supply reaches `until_supply` or at `until_timestamp`,
+    // whichever comes first
+    pub until_supply: Option<u64>,
+    pub until_timestamp: Option<i64>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HoldingCap {
+    // Share of the curve's max supply, in basis points
+    MaxSupplyBps(u16),
+    Amount(u64),
 }
 
 #[account]
@@ -17,6 +64,10 @@ pub struct BondingCurve {
     pub initial_price: u64,
     pub slope: u64,
     pub admin: Pubkey,
+    // Supply the curve can reach, and the part of it allocated to the creator at creation, which
+    // the creator paid the curve price for
+    pub max_supply: u64,
+    pub allocated_supply: u64,
 }
 
 #[account]
@@ -25,40 +76,195 @@ pub struct LiquidityPool {
     pub balance: u64, 
     pub accumulated_fees: u64,
     pub authority: Pubkey,
+    // Lamports traded in `volume_slot`, for the per-slot volume cap
+    pub volume_slot: u64,
+    pub slot_volume: u64,
 }
 
 #[account]
 pub struct UserPortfolio {
     pub user: Pubkey,
     pub token: Pubkey,
+    // Tokens bought and not yet sold, including those escrowed by the user's open sell orders, so
+    // that the holding and presale caps see them
     pub balance: u64,
 }
 
+// Intent nonces a user has spent. Also the delegate users approve on the token accounts their
+// signed intents trade from
+#[account]
+pub struct IntentNonces {
+    pub user: Pubkey,
+    // Nonces below `base` are spent, and bit i of `used` marks nonce base + i as spent
+    pub base: u64,
+    pub used: [u64; 4],
+}
+
+// Limit order against the curve. A buy escrows `amount` lamports in the order account, a sell
+// escrows `amount` tokens in the order's token account
+#[account]
+pub struct Order {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub id: u64,
+    pub side: TradeSide,
+    pub amount: u64,
+    // Spot price in lamports per base unit a buy waits to fall to, or a sell to rise to
+    pub trigger_price: u64,
+    // Fewest tokens a buy may mint, or fewest lamports a sell may pay the owner
+    pub limit: u64,
+    pub bump: u8,
+}
+
 #[account]
 pub struct GlobalState {
     pub admin: Pubkey,
+    pub paused: bool,
+    pub buy_fee_bps: u16,
+    pub sell_fee_bps: u16,
+    // Most lamports per buy, tokens per sell and lamports traded per token per slot; 0 is unlimited
+    pub max_buy_sol: u64,
+    pub max_sell_tokens: u64,
+    pub max_slot_volume: u64,
+}
+
+#[account]
+pub struct Vesting {
+    pub token: Pubkey,
+    pub beneficiary: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start_timestamp: i64,
+    pub cliff_seconds: i64,
+    pub duration_seconds: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct Observation {
+    pub timestamp: i64,
+    pub cumulative_price: u128,
+}
+
+#[account]
+pub struct PriceObservations {
+    pub token: Pubkey,
+    pub head: u16,
+    pub count: u16,
+    pub observations: [Observation; 64],
 }
 
 
 impl TokenInfo {
-    pub const LEN: usize = 8 + 32 + 32 + 10 + 200 + 8 + 32;
+    pub const LEN: usize = 8 + 32 + 32 + 10 + 200 + 8 + 32 + 8 + (1 + LaunchProtection::LEN) + 8
+        + (1 + HoldingLimit::LEN) + (1 + Presale::LEN);
+
+    // Buy fee of a public buy at `slot` and `timestamp`, given the protocol buy fee. Allowlisted
+    // presale buys pay the protocol fee. A protection window not opened yet opens with the next
+    // buy, which pays the start fee
+    pub fn buy_fee_bps(&self, base_fee_bps: u16, slot: u64, timestamp: i64) -> u16 {
+        if self.presale.is_some_and(|presale| timestamp < presale.ends_at) {
+            return base_fee_bps;
+        }
+        let Some(protection) = &self.launch_protection else {
+            return base_fee_bps;
+        };
+        let elapsed = match self.launch_slot {
+            0 => 0,
+            launch_slot => slot.saturating_sub(launch_slot),
+        };
+        if protection.start_fee_bps <= base_fee_bps || elapsed >= protection.duration_slots {
+            return base_fee_bps;
+        }
+
+        let decay = (protection.start_fee_bps - base_fee_bps) as u64 * elapsed / protection.duration_slots;
+        protection.start_fee_bps - decay as u16
+    }
+}
+
+impl Presale {
+    pub const LEN: usize = 32 + 8 + 8;
+
+    pub fn leaf(wallet: &Pubkey) -> [u8; 32] {
+        keccak::hash(wallet.as_ref()).0
+    }
+
+    pub fn node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        if a <= b {
+            keccak::hashv(&[a, b]).0
+        } else {
+            keccak::hashv(&[b, a]).0
+        }
+    }
+
+    pub fn is_allowlisted(&self, wallet: &Pubkey, proof: &[[u8; 32]]) -> bool {
+        let root = proof
+            .iter()
+            .fold(Self::leaf(wallet), |node, sibling| Self::node(&node, sibling));
+        root == self.merkle_root
+    }
+}
+
+impl HoldingLimit {
+    pub const LEN: usize = (1 + 8) + (1 + 8) + (1 + 8);
+
+    pub fn validate(&self) -> Result<()> {
+        let cap_valid = match self.cap {
+            HoldingCap::MaxSupplyBps(bps) => bps > 0 && bps <= 10_000,
+            HoldingCap::Amount(amount) => amount > 0,
+        };
+        require!(
+            cap_valid && (self.until_supply.is_some() || self.until_timestamp.is_some()),
+            ErrorCode::InvalidHoldingLimit
+        );
+        Ok(())
+    }
+
+    // Most tokens a wallet may hold at `supply` and `timestamp`, or None once the limit lifted
+    pub fn cap_at(&self, max_supply: u64, supply: u64, timestamp: i64) -> Option<u64> {
+        let lifted = self.until_supply.is_some_and(|until| supply >= until)
+            || self.until_timestamp.is_some_and(|until| timestamp >= until);
+        if lifted {
+            return None;
+        }
+
+        Some(match self.cap {
+            HoldingCap::MaxSupplyBps(bps) => (max_supply as u128 * bps as u128 / 10_000) as u64,
+            HoldingCap::Amount(amount) => amount,
+        })
+    }
+}
+
+impl LaunchProtection {
+    pub const LEN: usize = 2 + 8 + 1;
+    // Protocol caps on what a creator may configure
+    pub const MAX_START_FEE_BPS: u16 = 5_000;
+    pub const MAX_DURATION_SLOTS: u64 = 9_000;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.start_fee_bps <= Self::MAX_START_FEE_BPS
+                && self.duration_slots > 0
+                && self.duration_slots <= Self::MAX_DURATION_SLOTS,
+            ErrorCode::InvalidLaunchProtection
+        );
+        Ok(())
+    }
 }
 
 impl BondingCurve {
-    pub const LEN: usize = 8 + 32 + 8 + 8 + 32;
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 32 + 8 + 8;
+    pub const DEFAULT_MAX_SUPPLY: u64 = 100_000;
 
-    
     pub fn calculate_price(&self, supply: u64) -> Result<u64> {
-        let price = self.initial_price
-            .checked_mul(
-                (self.slope as u128)
-                    .checked_mul(supply as u128)
-                    .ok_or(ErrorCode::ArithmeticOverflow)?
-                    .checked_div(1_000_000)
-                    .ok_or(ErrorCode::ArithmeticOverflow)? as u64
-            )
-            .ok_or(ErrorCode::ArithmeticOverflow)?;
-        Ok(price)
+        // Spot price is the derivative of the curve integrated by the trade handlers:
+        // P(n) = P₀ * e^(k * n), in lamports per base token unit
+        let k = self.slope as f64 / 1e6;
+        let price = self.initial_price as f64 * (k * supply as f64).exp();
+
+        if !price.is_finite() || price >= u64::MAX as f64 {
+            return Err(ErrorCode::ArithmeticOverflow.into());
+        }
+        Ok(price.floor() as u64)
     }
 
     pub fn update_params(&mut self, initial_price: u64, slope: u64) -> Result<()> {
@@ -69,7 +275,21 @@ impl BondingCurve {
 }
 
 impl LiquidityPool {
-    pub const LEN: usize = 8 + 32 + 8 + 8 + 32;
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 32 + 8 + 8;
+
+    // Adds `amount` lamports to the volume traded in `slot`, within `max_slot_volume` unless 0
+    pub fn record_volume(&mut self, slot: u64, amount: u64, max_slot_volume: u64) -> Result<()> {
+        if self.volume_slot != slot {
+            self.volume_slot = slot;
+            self.slot_volume = 0;
+        }
+        self.slot_volume = self.slot_volume.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(
+            max_slot_volume == 0 || self.slot_volume <= max_slot_volume,
+            ErrorCode::SlotVolumeExceeded
+        );
+        Ok(())
+    }
 
     pub fn add_liquidity(&mut self, amount: u64) -> Result<()> {
         self.balance = self.balance.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
@@ -92,4 +312,174 @@ impl LiquidityPool {
 
 impl UserPortfolio {
     pub const LEN: usize = 8 + 32 + 32 + 8;
+}
+
+impl IntentNonces {
+    pub const LEN: usize = 8 + 32 + 8 + 32;
+    pub const WINDOW: u64 = 256;
+
+    // Spends `nonce`. One past the window slides the window up to it, which spends every nonce
+    // left below it
+    pub fn spend(&mut self, nonce: u64) -> Result<()> {
+        require!(nonce >= self.base, ErrorCode::NonceUsed);
+        let past_window = (nonce - self.base).saturating_sub(Self::WINDOW - 1);
+        if past_window > 0 {
+            let mut used = [0u64; 4];
+            for offset in past_window..Self::WINDOW {
+                if self.is_used(offset) {
+                    let offset = offset - past_window;
+                    used[offset as usize / 64] |= 1 << (offset % 64);
+                }
+            }
+            self.used = used;
+            self.base += past_window;
+        }
+
+        let offset = nonce - self.base;
+        require!(!self.is_used(offset), ErrorCode::NonceUsed);
+        self.used[offset as usize / 64] |= 1 << (offset % 64);
+        Ok(())
+    }
+
+    fn is_used(&self, offset: u64) -> bool {
+        self.used[offset as usize / 64] & (1 << (offset % 64)) != 0
+    }
+}
+
+impl Order {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1 + 8 + 8 + 8 + 1;
+    // Share of the lamports traded that goes to the keeper filling the order
+    pub const KEEPER_REWARD_BPS: u16 = 10;
+
+    pub fn is_triggered(&self, spot_price: u64) -> bool {
+        match self.side {
+            TradeSide::Buy => spot_price <= self.trigger_price,
+            TradeSide::Sell => spot_price >= self.trigger_price,
+        }
+    }
+}
+
+impl Vesting {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8;
+    // At most 10% of the max supply can be allocated to the creator
+    pub const MAX_ALLOCATION_BPS: u16 = 1_000;
+
+    // Nothing vests before the cliff, then the allocation vests linearly until the end
+    pub fn vested_amount(&self, timestamp: i64) -> u64 {
+        let elapsed = timestamp.saturating_sub(self.start_timestamp);
+        if elapsed < self.cliff_seconds {
+            return 0;
+        }
+        if elapsed >= self.duration_seconds {
+            return self.total_amount;
+        }
+        (self.total_amount as u128 * elapsed as u128 / self.duration_seconds as u128) as u64
+    }
+}
+
+impl GlobalState {
+    pub const LEN: usize = 8 + 32 + 1 + 2 + 2 + 8 + 8 + 8;
+    pub const DEFAULT_FEE_BPS: u16 = 100;
+    pub const MAX_FEE_BPS: u16 = 1_000;
+
+    pub fn buy_fee(&self, amount_sol: u64) -> u64 {
+        fee_for(amount_sol, self.buy_fee_bps)
+    }
+
+    pub fn sell_fee(&self, amount_sol: u64) -> u64 {
+        fee_for(amount_sol, self.sell_fee_bps)
+    }
+}
+
+pub fn fee_for(amount: u64, fee_bps: u16) -> u64 {
+    // Cannot overflow: fee_bps is capped below 10_000
+    (amount as u128 * fee_bps as u128 / 10_000) as u64
+}
+
+impl PriceObservations {
+    pub const CAPACITY: usize = 64;
+    pub const LEN: usize = 8 + 32 + 2 + 2 + Self::CAPACITY * (8 + 16);
+
+    pub fn initialize(&mut self, token: Pubkey, timestamp: i64) {
+        self.token = token;
+        self.head = 0;
+        self.count = 1;
+        self.observations[0] = Observation {
+            timestamp,
+            cumulative_price: 0,
+        };
+    }
+
+    pub fn latest(&self) -> Observation {
+        self.observations[self.head as usize]
+    }
+
+    // Accumulates `price`, the price in effect since the latest observation, up to `timestamp`
+    // and stores the result as the newest observation, overwriting the oldest one when full.
+    pub fn record(&mut self, timestamp: i64, price: u64) -> Result<()> {
+        let latest = self.latest();
+        if timestamp <= latest.timestamp {
+            return Ok(());
+        }
+        let cumulative_price = accumulate(latest, timestamp, price)?;
+
+        self.head = ((self.head as usize + 1) % Self::CAPACITY) as u16;
+        self.observations[self.head as usize] = Observation {
+            timestamp,
+            cumulative_price,
+        };
+        self.count = (self.count as usize + 1).min(Self::CAPACITY) as u16;
+        Ok(())
+    }
+
+    // Time-weighted average price over the last `window` seconds, where `price` is the spot
+    // price in effect since the latest observation.
+    pub fn twap(&self, now: i64, price: u64, window: u32) -> Result<u64> {
+        require!(window > 0, ErrorCode::InvalidTwapWindow);
+        let latest = self.latest();
+        let target = now
+            .checked_sub(window as i64)
+            .ok_or(ErrorCode::ArithmeticUnderflow)?;
+
+        let cumulative_now = accumulate(latest, now, price)?;
+        let cumulative_target = if latest.timestamp <= target {
+            accumulate(latest, target, price)?
+        } else {
+            self.cumulative_at(target)?
+        };
+
+        let average = cumulative_now
+            .checked_sub(cumulative_target)
+            .ok_or(ErrorCode::ArithmeticUnderflow)?
+            / window as u128;
+        u64::try_from(average).map_err(|_| ErrorCode::ArithmeticOverflow.into())
+    }
+
+    // Interpolates the cumulative price at `target`, which must lie between two stored
+    // observations. The price is constant between consecutive observations.
+    fn cumulative_at(&self, target: i64) -> Result<u128> {
+        let mut newer = self.latest();
+        for offset in 1..self.count as usize {
+            let index = (self.head as usize + Self::CAPACITY - offset) % Self::CAPACITY;
+            let older = self.observations[index];
+            if older.timestamp <= target {
+                let elapsed = (newer.timestamp - older.timestamp) as u128;
+                let delta = newer.cumulative_price - older.cumulative_price;
+                return Ok(older.cumulative_price
+                    + delta * (target - older.timestamp) as u128 / elapsed);
+            }
+            newer = older;
+        }
+        err!(ErrorCode::InsufficientPriceHistory)
+    }
+}
+
+fn accumulate(observation: Observation, timestamp: i64, price: u64) -> Result<u128> {
+    let elapsed = timestamp
+        .checked_sub(observation.timestamp)
+        .ok_or(ErrorCode::ArithmeticUnderflow)? as u128;
+    (price as u128)
+        .checked_mul(elapsed)
+        .and_then(|weighted| observation.cumulative_price.checked_add(weighted))
+        .ok_or(ErrorCode::ArithmeticOverflow.into())
 }
\ No newline at end of file
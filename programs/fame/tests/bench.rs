@@ -0,0 +1,250 @@
+//! Compute-unit benchmarks of the program instructions.
+//!
+//! Runs each instruction in the in-process SVM across representative curve supplies and fails
+//! when any of them consumes more than its budget in `tests/compute_units.md`. The budgets there
+//! are the units recorded against the SBF build plus [`HEADROOM_PERCENT`]; a measurement the
+//! report has no budget for fails too, so the gate never passes without a baseline.
+//!
+//! Compute units are only metered for the compiled program, so the benchmark needs the SBF
+//! build and is skipped by a plain `cargo test`. CI runs it in `.github/workflows/bench.yml`:
+//!
+//! ```text
+//! anchor build
+//! SBF_OUT_DIR=target/deploy cargo test -p fame --test bench -- --ignored
+//! ```
+//!
+//! A change that is meant to move the numbers records a new baseline by running the same
+//! command with `FAME_BENCH_RECORD=1`, which rewrites the report, and commits it with the change.
+
+mod common;
+
+use std::fmt::Write;
+
+use common::Env;
+use fame::instructions::calculate_sol_to_return;
+use fame::state::{BondingCurve, LiquidityPool, TokenInfo};
+use fame_client::instructions::CreateTokenArgs;
+use fame_client::{instructions, pda};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::{Keypair, Signer};
+
+/// Supplies, on top of the benchmark holder's tokens, at which the trades are measured. With the
+/// default curve the spot price grows e-fold about every 10,900 units.
+const SUPPLIES: [u64; 4] = [0, 1_000, 10_000, 90_000];
+
+/// Units over the recorded baseline a measurement may consume before it counts as a regression.
+/// Covers the noise of the curve math at slightly different supplies between runs.
+const HEADROOM_PERCENT: u64 = 10;
+
+const REPORT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/compute_units.md");
+
+struct Measurement {
+    instruction: &'static str,
+    supply: Option<u64>,
+    units: u64,
+}
+
+#[tokio::test]
+#[ignore = "needs the SBF build of the program, see the module docs"]
+async fn instructions_stay_within_compute_budgets() {
+    assert!(
+        std::env::var("SBF_OUT_DIR").is_ok() || std::env::var("BPF_OUT_DIR").is_ok(),
+        "compute units are only metered for the SBF build; set SBF_OUT_DIR to the directory \
+         containing fame.so"
+    );
+
+    let mut env = Env::new().await;
+    let mut measurements = Vec::new();
+    let mut measure = |instruction, supply, units| {
+        measurements.push(Measurement {
+            instruction,
+            supply,
+            units,
+        })
+    };
+
+    let creator = env.user().await;
+    let mint = Keypair::new();
+    let args = CreateTokenArgs {
+        name: "Fame".to_string(),
+        symbol: "FAME".to_string(),
+        social_account_url: "https://x.com/fame".to_string(),
+        ..CreateTokenArgs::default()
+    };
+    let instruction = instructions::create_token(&creator.pubkey(), &mint.pubkey(), args);
+    let units = env
+        .send_metered(&[instruction], &[&creator, &mint])
+        .await
+        .unwrap();
+    measure("create_token", None, units);
+    let mint = mint.pubkey();
+
+    let holder = env.user().await;
+    env.buy(&holder, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    let held = env.token_balance(&holder.pubkey(), &mint).await;
+
+    for supply in SUPPLIES {
+        move_curve(&mut env, &mint, held + supply).await;
+
+        // Pays for the creation of the buyer's token account and portfolio
+        let buyer = env.user().await;
+        let instruction = instructions::mint_token(&buyer.pubkey(), &mint, LAMPORTS_PER_SOL / 10);
+        let units = env.send_metered(&[instruction], &[&buyer]).await.unwrap();
+        measure("mint_token (first buy)", Some(supply), units);
+
+        let instruction = instructions::mint_token(&holder.pubkey(), &mint, LAMPORTS_PER_SOL / 10);
+        let units = env.send_metered(&[instruction], &[&holder]).await.unwrap();
+        measure("mint_token", Some(supply), units);
+
+        let instruction = instructions::burn_token(&holder.pubkey(), &mint, 1);
+        let units = env.send_metered(&[instruction], &[&holder]).await.unwrap();
+        measure("burn_token", Some(supply), units);
+
+        let payer = env.context.payer.insecure_clone();
+        let instruction = instructions::get_quote(&mint, LAMPORTS_PER_SOL, 1);
+        let units = env.send_metered(&[instruction], &[&payer]).await.unwrap();
+        measure("get_quote", Some(supply), units);
+
+        let instruction = instructions::verify_pool(&mint);
+        let units = env.send_metered(&[instruction], &[&payer]).await.unwrap();
+        measure("verify_pool", Some(supply), units);
+    }
+
+    if std::env::var("FAME_BENCH_RECORD").is_ok() {
+        std::fs::write(REPORT, report(&measurements)).unwrap();
+        return;
+    }
+
+    let recorded = std::fs::read_to_string(REPORT).unwrap();
+    let budgets = budgets(&recorded);
+    let over_budget: Vec<String> = measurements
+        .iter()
+        .filter_map(|measurement| {
+            let budget = budgets
+                .iter()
+                .find(|(instruction, supply, _)| {
+                    *instruction == measurement.instruction && *supply == measurement.supply
+                })
+                .map(|(_, _, budget)| *budget);
+            match budget {
+                Some(budget) if measurement.units <= budget => None,
+                Some(budget) => Some(format!(
+                    "{} at supply {:?}: {} > {budget}",
+                    measurement.instruction, measurement.supply, measurement.units
+                )),
+                None => Some(format!(
+                    "{} at supply {:?}: {} units, no recorded budget",
+                    measurement.instruction, measurement.supply, measurement.units
+                )),
+            }
+        })
+        .collect();
+    assert!(
+        over_budget.is_empty(),
+        "over budget, record a new baseline with FAME_BENCH_RECORD=1 if this is intended:\n{}",
+        over_budget.join("\n")
+    );
+}
+
+#[test]
+fn budgets_round_trip_through_the_report() {
+    let measurements = [
+        Measurement {
+            instruction: "create_token",
+            supply: None,
+            units: 40_001,
+        },
+        Measurement {
+            instruction: "mint_token",
+            supply: Some(10_000),
+            units: 30_000,
+        },
+    ];
+    assert_eq!(
+        budgets(&report(&measurements)),
+        [
+            ("create_token", None, 44_002),
+            ("mint_token", Some(10_000), 33_000)
+        ]
+    );
+}
+
+/// Moves the curve of `mint` to `supply`, with the pool holding exactly the reserves it needs.
+async fn move_curve(env: &mut Env, mint: &Pubkey, supply: u64) {
+    let bonding_curve: BondingCurve = env.account(&pda::bonding_curve(mint).0).await;
+    let reserves = calculate_sol_to_return(&bonding_curve, supply, supply).unwrap();
+    env.edit(&pda::token_info(mint).0, |token_info: &mut TokenInfo| {
+        token_info.total_supply = supply;
+    })
+    .await;
+
+    let pool = pda::liquidity_pool(mint).0;
+    let mut fees = 0;
+    env.edit(&pool, |liquidity_pool: &mut LiquidityPool| {
+        liquidity_pool.balance = reserves;
+        fees = liquidity_pool.accumulated_fees;
+    })
+    .await;
+    let rent: Rent = env.context.banks_client.get_sysvar().await.unwrap();
+    let rent = rent.minimum_balance(LiquidityPool::LEN);
+    env.set_lamports(&pool, reserves + fees + rent).await;
+}
+
+/// Budget of a measurement recorded at `units`.
+fn budget(units: u64) -> u64 {
+    units + (units * HEADROOM_PERCENT).div_ceil(100)
+}
+
+/// The `(instruction, supply, budget)` rows of a report written by [`report`].
+fn budgets(report: &str) -> Vec<(&str, Option<u64>, u64)> {
+    report
+        .lines()
+        .filter_map(|line| {
+            let cells: Vec<&str> = line
+                .strip_prefix('|')?
+                .strip_suffix('|')?
+                .split('|')
+                .map(str::trim)
+                .collect();
+            let [instruction, supply, _units, budget] = cells[..] else {
+                return None;
+            };
+            let budget = budget.parse().ok()?;
+            let supply = match supply {
+                "-" => None,
+                supply => Some(supply.parse().ok()?),
+            };
+            Some((instruction, supply, budget))
+        })
+        .collect()
+}
+
+fn report(measurements: &[Measurement]) -> String {
+    let mut report = format!(
+        "# Compute units\n\
+         \n\
+         Generated by `tests/bench.rs` against the SBF build; see its module docs to regenerate.\n\
+         Supplies are on top of the benchmark holder's tokens. Budgets are the recorded units plus\n\
+         {HEADROOM_PERCENT}%.\n\
+         \n\
+         | instruction | supply | units | budget |\n\
+         | --- | ---: | ---: | ---: |\n",
+    );
+    for measurement in measurements {
+        let supply = measurement
+            .supply
+            .map_or("-".to_string(), |supply| supply.to_string());
+        writeln!(
+            report,
+            "| {} | {} | {} | {} |",
+            measurement.instruction,
+            supply,
+            measurement.units,
+            budget(measurement.units)
+        )
+        .unwrap();
+    }
+    report
+}
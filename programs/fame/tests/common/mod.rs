@@ -0,0 +1,334 @@
+//! In-process SVM harness shared by the integration tests.
+//!
+//! By default the program runs natively through its Anchor entrypoint. When `BPF_OUT_DIR` or
+//! `SBF_OUT_DIR` points at a directory containing `fame.so` (as `cargo test-sbf` sets it up), the
+//! compiled program is loaded instead.
+
+// Each test crate uses its own subset of the helpers
+#![allow(dead_code)]
+
+use anchor_lang::prelude::AccountInfo;
+use std::collections::HashSet;
+
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::{AccountDeserialize, AccountSerialize};
+use anchor_spl::token::TokenAccount;
+use fame::errors::ErrorCode;
+use fame::state::{BondingCurve, GlobalState, LiquidityPool, TokenInfo};
+use fame_client::amm::{AccountMap, Amm, FameAmm};
+use fame_client::instructions::CreateTokenArgs;
+use fame_client::quote::Quoter;
+use fame_client::{instructions, pda};
+use solana_program_test::{processor, ProgramTest, ProgramTestContext};
+use solana_sdk::account::AccountSharedData;
+use solana_sdk::clock::Clock;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+pub type TransactionResult<T> = Result<T, TransactionError>;
+
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    // The Anchor entrypoint ties the slice and account lifetimes together, which the native
+    // processor signature does not. The copies share their data, so leaking one is harmless.
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    fame::entry(program_id, accounts, data)
+}
+
+pub struct Env {
+    pub context: ProgramTestContext,
+    pub admin: Keypair,
+    sent: HashSet<Signature>,
+}
+
+impl Env {
+    /// A fresh SVM with the program state initialized by a funded admin.
+    pub async fn new() -> Self {
+        let program_test = ProgramTest::new("fame", fame::ID, processor!(process_instruction));
+        let context = program_test.start_with_context().await;
+        let admin = Keypair::new();
+        let mut env = Self {
+            context,
+            admin,
+            sent: HashSet::new(),
+        };
+
+        env.fund(&env.admin.pubkey(), 10 * LAMPORTS_PER_SOL).await;
+        let admin = env.admin.insecure_clone();
+        env.send(&[instructions::initialize(&admin.pubkey())], &[&admin])
+            .await
+            .unwrap();
+        env
+    }
+
+    /// Sends a transaction paid by the first signer.
+    ///
+    /// Uses the bank's current blockhash, which is cheap to get. Only when an identical
+    /// transaction was already sent with it, which the bank would answer with the earlier result
+    /// instead of executing again, does it wait for the next one.
+    pub async fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> TransactionResult<()> {
+        self.send_metered(instructions, signers).await.map(|_| ())
+    }
+
+    /// Sends a transaction like [`Env::send`] and returns the compute units it consumed.
+    pub async fn send_metered(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> TransactionResult<u64> {
+        let mut transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signers[0].pubkey()),
+            signers,
+            self.latest_blockhash().await,
+        );
+        if !self.sent.insert(transaction.signatures[0]) {
+            let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+            transaction.sign(signers, blockhash);
+            self.sent.insert(transaction.signatures[0]);
+        }
+        let processed = self
+            .context
+            .banks_client
+            .process_transaction_with_metadata(transaction)
+            .await
+            .unwrap_or_else(|err| panic!("transaction not processed: {err}"));
+        processed.result?;
+        Ok(processed
+            .metadata
+            .map(|metadata| metadata.compute_units_consumed)
+            .unwrap_or_default())
+    }
+
+    /// Runs a view instruction and returns its return data.
+    pub async fn view(&mut self, instruction: Instruction) -> TransactionResult<Vec<u8>> {
+        let payer = self.context.payer.insecure_clone();
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer],
+            self.latest_blockhash().await,
+        );
+        let simulation = self
+            .context
+            .banks_client
+            .simulate_transaction(transaction)
+            .await
+            .unwrap();
+        simulation.result.unwrap()?;
+        Ok(simulation
+            .simulation_details
+            .and_then(|details| details.return_data)
+            .map(|return_data| return_data.data)
+            .unwrap_or_default())
+    }
+
+    async fn latest_blockhash(&mut self) -> Hash {
+        let blockhash = self
+            .context
+            .banks_client
+            .get_latest_blockhash()
+            .await
+            .unwrap();
+        self.context.last_blockhash = blockhash;
+        blockhash
+    }
+
+    pub async fn fund(&mut self, address: &Pubkey, lamports: u64) {
+        let payer = self.context.payer.insecure_clone();
+        let instruction = system_instruction::transfer(&payer.pubkey(), address, lamports);
+        self.send(&[instruction], &[&payer]).await.unwrap();
+    }
+
+    pub async fn user(&mut self) -> Keypair {
+        let user = Keypair::new();
+        self.fund(&user.pubkey(), 10 * LAMPORTS_PER_SOL).await;
+        user
+    }
+
+    pub async fn create_token(&mut self, creator: &Keypair) -> TransactionResult<Pubkey> {
+        self.create_token_with(creator, "Fame", "FAME", "https://x.com/fame")
+            .await
+    }
+
+    pub async fn create_token_with(
+        &mut self,
+        creator: &Keypair,
+        name: &str,
+        symbol: &str,
+        social_account_url: &str,
+    ) -> TransactionResult<Pubkey> {
+        let args = CreateTokenArgs {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            social_account_url: social_account_url.to_string(),
+            ..CreateTokenArgs::default()
+        };
+        self.create_token_with_args(creator, args).await
+    }
+
+    pub async fn create_token_with_args(
+        &mut self,
+        creator: &Keypair,
+        args: CreateTokenArgs,
+    ) -> TransactionResult<Pubkey> {
+        let mint = Keypair::new();
+        let instruction = instructions::create_token(&creator.pubkey(), &mint.pubkey(), args);
+        self.send(&[instruction], &[creator, &mint]).await?;
+        Ok(mint.pubkey())
+    }
+
+    pub async fn buy(
+        &mut self,
+        user: &Keypair,
+        mint: &Pubkey,
+        amount_sol: u64,
+    ) -> TransactionResult<()> {
+        let instruction = instructions::mint_token(&user.pubkey(), mint, amount_sol);
+        self.send(&[instruction], &[user]).await
+    }
+
+    pub async fn sell(
+        &mut self,
+        user: &Keypair,
+        mint: &Pubkey,
+        amount_tokens: u64,
+    ) -> TransactionResult<()> {
+        let instruction = instructions::burn_token(&user.pubkey(), mint, amount_tokens);
+        self.send(&[instruction], &[user]).await
+    }
+
+    pub async fn lamports(&mut self, address: &Pubkey) -> u64 {
+        self.context
+            .banks_client
+            .get_balance(*address)
+            .await
+            .unwrap()
+    }
+
+    pub async fn account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> T {
+        self.try_account(address).await.expect("account exists")
+    }
+
+    pub async fn try_account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> Option<T> {
+        let account = self
+            .context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()?;
+        Some(T::try_deserialize(&mut account.data.as_slice()).unwrap())
+    }
+
+    pub async fn token_balance(&mut self, owner: &Pubkey, mint: &Pubkey) -> u64 {
+        let token_account: TokenAccount = self.account(&pda::token_account(owner, mint)).await;
+        token_account.amount
+    }
+
+    /// Overwrites the state of a program account, to reach states trades cannot produce.
+    pub async fn edit<T: AccountDeserialize + AccountSerialize>(
+        &mut self,
+        address: &Pubkey,
+        edit: impl FnOnce(&mut T),
+    ) {
+        let mut account = self
+            .context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .expect("account exists");
+        let mut state = T::try_deserialize(&mut account.data.as_slice()).unwrap();
+        edit(&mut state);
+        let mut data = Vec::new();
+        state.try_serialize(&mut data).unwrap();
+        account.data[..data.len()].copy_from_slice(&data);
+        self.context
+            .set_account(address, &AccountSharedData::from(account));
+    }
+
+    /// Overwrites the lamports of an account.
+    pub async fn set_lamports(&mut self, address: &Pubkey, lamports: u64) {
+        let mut account = self
+            .context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .expect("account exists");
+        account.lamports = lamports;
+        self.context
+            .set_account(address, &AccountSharedData::from(account));
+    }
+
+    pub async fn quoter(&mut self, mint: &Pubkey) -> Quoter {
+        let global_state: GlobalState = self.account(&pda::global_state().0).await;
+        let token_info: TokenInfo = self.account(&pda::token_info(mint).0).await;
+        let bonding_curve: BondingCurve = self.account(&pda::bonding_curve(mint).0).await;
+        let liquidity_pool: LiquidityPool = self.account(&pda::liquidity_pool(mint).0).await;
+        let clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        Quoter::new(
+            global_state,
+            bonding_curve,
+            &token_info,
+            &liquidity_pool,
+            &clock,
+        )
+    }
+
+    /// The aggregator market of `mint`, updated from the bank.
+    pub async fn amm(&mut self, mint: &Pubkey) -> FameAmm {
+        let mut amm = FameAmm::new(*mint);
+        let mut accounts = AccountMap::new();
+        for address in amm.accounts_to_update() {
+            let account = self
+                .context
+                .banks_client
+                .get_account(address)
+                .await
+                .unwrap()
+                .expect("account exists");
+            accounts.insert(address, account.data);
+        }
+        amm.update(&accounts).unwrap();
+        amm
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        let clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp
+    }
+
+    pub async fn advance_clock(&mut self, seconds: i64) {
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += seconds;
+        self.context.set_sysvar(&clock);
+    }
+
+    pub async fn set_slot(&mut self, slot: u64) {
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.slot = slot;
+        self.context.set_sysvar(&clock);
+    }
+}
+
+pub fn assert_error<T: std::fmt::Debug>(result: TransactionResult<T>, expected: ErrorCode) {
+    match result {
+        Err(TransactionError::InstructionError(_, InstructionError::Custom(code))) => {
+            assert_eq!(code, u32::from(expected), "expected {expected:?}")
+        }
+        other => panic!("expected {expected:?}, got {other:?}"),
+    }
+}
@@ -0,0 +1,228 @@
+//! Property-based tests of the bonding curve math shared by the trade handlers and quotes.
+
+use fame::errors::ErrorCode;
+use fame::instructions::{calculate_sol_to_return, calculate_tokens_to_mint};
+use fame::state::{BondingCurve, GlobalState};
+use proptest::prelude::*;
+
+fn curve(initial_price: u64, slope: u64) -> BondingCurve {
+    BondingCurve {
+        token: Default::default(),
+        initial_price,
+        slope,
+        admin: Default::default(),
+        max_supply: u64::MAX,
+        allocated_supply: 0,
+    }
+}
+
+fn global_state(buy_fee_bps: u16, sell_fee_bps: u16) -> GlobalState {
+    GlobalState {
+        admin: Default::default(),
+        paused: false,
+        buy_fee_bps,
+        sell_fee_bps,
+        max_buy_sol: 0,
+        max_sell_tokens: 0,
+        max_slot_volume: 0,
+    }
+}
+
+/// Any `u64`, spread evenly over bit lengths so small and large magnitudes are equally likely.
+fn magnitude() -> impl Strategy<Value = u64> {
+    (0..=64u32, any::<u64>()).prop_map(|(bits, value)| value.checked_shr(64 - bits).unwrap_or(0))
+}
+
+/// Slopes that keep the curve representable over a useful supply, with the full range mixed in.
+fn slope() -> impl Strategy<Value = u64> {
+    prop_oneof![3 => 1..=10_000u64, 1 => magnitude()]
+}
+
+/// Supplies the curves above can price, with the full range mixed in.
+fn supply() -> impl Strategy<Value = u64> {
+    prop_oneof![3 => 0..=1_000_000u64, 1 => magnitude()]
+}
+
+#[derive(Clone, Debug)]
+enum Trade {
+    Buy(u64),
+    /// Sells this fraction, in basis points, of the outstanding supply.
+    Sell(u16),
+}
+
+fn trade() -> impl Strategy<Value = Trade> {
+    prop_oneof![
+        magnitude().prop_map(Trade::Buy),
+        (1..=10_000u16).prop_map(Trade::Sell),
+    ]
+}
+
+proptest! {
+    // Most of the full parameter range prices nothing, so run enough cases to hit real trades
+    #![proptest_config(ProptestConfig::with_cases(4_096))]
+
+    #[test]
+    fn buy_then_sell_never_returns_more_than_paid(
+        initial_price in magnitude(),
+        slope in slope(),
+        supply in supply(),
+        amount_sol in magnitude(),
+    ) {
+        let curve = curve(initial_price, slope);
+        let Ok(tokens) = calculate_tokens_to_mint(&curve, supply, amount_sol) else {
+            return Ok(());
+        };
+        let Some(new_supply) = supply.checked_add(tokens) else {
+            return Ok(());
+        };
+        let refund = calculate_sol_to_return(&curve, new_supply, tokens)?;
+        prop_assert!(refund <= amount_sol, "paid {amount_sol}, refunded {refund}");
+    }
+
+    #[test]
+    fn reserves_cover_selling_back_the_whole_supply(
+        initial_price in magnitude().prop_map(|price| price.max(1)),
+        slope in slope().prop_map(|slope| slope.max(1)),
+        buy_fee_bps in 0..=GlobalState::MAX_FEE_BPS,
+        sell_fee_bps in 0..=GlobalState::MAX_FEE_BPS,
+        trades in prop::collection::vec(trade(), 1..32),
+    ) {
+        let curve = curve(initial_price, slope);
+        // Mirrors the bookkeeping of mint_token and burn_token
+        let global_state = global_state(buy_fee_bps, sell_fee_bps);
+        let mut supply = 0u64;
+        let mut balance = 0u64;
+        for trade in trades {
+            match trade {
+                Trade::Buy(amount_sol) => {
+                    let fee = global_state.buy_fee(amount_sol);
+                    let Ok(tokens) = calculate_tokens_to_mint(&curve, supply, amount_sol - fee) else {
+                        continue;
+                    };
+                    let (Some(new_supply), Some(new_balance)) =
+                        (supply.checked_add(tokens), balance.checked_add(amount_sol - fee))
+                    else {
+                        continue;
+                    };
+                    supply = new_supply;
+                    balance = new_balance;
+                }
+                Trade::Sell(bps) => {
+                    let amount_tokens = (supply as u128 * bps as u128 / 10_000) as u64;
+                    let sol_to_return = calculate_sol_to_return(&curve, supply, amount_tokens)?;
+                    prop_assert!(sol_to_return <= balance, "reserves {balance}, owed {sol_to_return}");
+                    supply -= amount_tokens;
+                    balance -= sol_to_return;
+                }
+            }
+            let owed = calculate_sol_to_return(&curve, supply, supply)?;
+            prop_assert!(owed <= balance, "reserves {balance}, owed {owed} for supply {supply}");
+        }
+    }
+
+    #[test]
+    fn buys_are_monotonic_in_amount(
+        initial_price in magnitude(),
+        slope in slope(),
+        supply in supply(),
+        a in magnitude(),
+        b in magnitude(),
+    ) {
+        let curve = curve(initial_price, slope);
+        let (low, high) = (a.min(b), a.max(b));
+        if let (Ok(low_out), Ok(high_out)) = (
+            calculate_tokens_to_mint(&curve, supply, low),
+            calculate_tokens_to_mint(&curve, supply, high),
+        ) {
+            prop_assert!(low_out <= high_out, "{low} -> {low_out}, {high} -> {high_out}");
+        }
+    }
+
+    #[test]
+    fn sells_are_monotonic_in_amount(
+        initial_price in magnitude(),
+        slope in slope(),
+        supply in supply(),
+        a in supply(),
+        b in supply(),
+    ) {
+        let curve = curve(initial_price, slope);
+        let (low, high) = (a.min(b).min(supply), a.max(b).min(supply));
+        if let (Ok(low_out), Ok(high_out)) = (
+            calculate_sol_to_return(&curve, supply, low),
+            calculate_sol_to_return(&curve, supply, high),
+        ) {
+            prop_assert!(low_out <= high_out, "{low} -> {low_out}, {high} -> {high_out}");
+        }
+    }
+
+    #[test]
+    fn buys_get_no_cheaper_as_supply_grows(
+        initial_price in magnitude(),
+        slope in slope(),
+        a in supply(),
+        b in supply(),
+        amount_sol in magnitude(),
+    ) {
+        let curve = curve(initial_price, slope);
+        let (low, high) = (a.min(b), a.max(b));
+        if let (Ok(low_out), Ok(high_out)) = (
+            calculate_tokens_to_mint(&curve, low, amount_sol),
+            calculate_tokens_to_mint(&curve, high, amount_sol),
+        ) {
+            prop_assert!(high_out <= low_out, "at {low}: {low_out}, at {high}: {high_out}");
+        }
+        if let (Ok(low_price), Ok(high_price)) = (curve.calculate_price(low), curve.calculate_price(high)) {
+            prop_assert!(low_price <= high_price);
+        }
+    }
+
+    #[test]
+    fn selling_more_than_the_supply_fails(
+        initial_price in magnitude(),
+        slope in slope(),
+        supply in supply(),
+        excess in magnitude(),
+    ) {
+        let curve = curve(initial_price, slope);
+        let amount_tokens = supply.saturating_add(excess.max(1));
+        prop_assume!(amount_tokens > supply);
+        prop_assert!(calculate_sol_to_return(&curve, supply, amount_tokens).is_err());
+    }
+
+    #[test]
+    fn the_creator_allocation_sells_back_along_the_curve(
+        initial_price in magnitude(),
+        slope in slope(),
+        allocated in supply(),
+        above in supply(),
+        amount_tokens in magnitude(),
+    ) {
+        // The creator paid the curve price for the allocation, so it is priced like any supply
+        let unallocated = curve(initial_price, slope);
+        let mut curve = curve(initial_price, slope);
+        curve.allocated_supply = allocated;
+        let Some(supply) = allocated.checked_add(above) else {
+            return Ok(());
+        };
+        prop_assert_eq!(
+            calculate_sol_to_return(&curve, supply, amount_tokens),
+            calculate_sol_to_return(&unallocated, supply, amount_tokens)
+        );
+    }
+}
+
+#[test]
+fn degenerate_curves_are_rejected() {
+    let expected: anchor_lang::error::Error = ErrorCode::InvalidBondingCurveParameters.into();
+    for curve in [curve(0, 92), curve(10_000_000, 0)] {
+        assert_eq!(
+            calculate_tokens_to_mint(&curve, 0, 1_000_000_000).unwrap_err(),
+            expected
+        );
+        assert_eq!(
+            calculate_sol_to_return(&curve, 10, 5).unwrap_err(),
+            expected
+        );
+    }
+}
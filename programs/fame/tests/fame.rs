@@ -0,0 +1,2179 @@
+//! Integration tests of the program instructions and every `ErrorCode` path.
+
+mod common;
+
+use anchor_lang::AnchorDeserialize;
+use anchor_spl::token::spl_token::{self, native_mint};
+use anchor_spl::token::Mint;
+use common::{assert_error, Env};
+use fame::errors::ErrorCode;
+use fame::instructions::{calculate_sol_to_return, calculate_tokens_to_mint, CreatorAllocation};
+use fame::state::{
+    fee_for, BondingCurve, GlobalState, IntentNonces, LaunchProtection, LiquidityPool, Order,
+    PriceObservations, TokenInfo, UserPortfolio, Vesting,
+};
+use fame_client::allowlist::Allowlist;
+use fame_client::amm::{Amm, QuoteParams, SwapParams};
+use fame_client::instructions::{
+    CreateTokenArgs, HoldingCap, HoldingLimit, Presale, TradeIntent, TradeLeg, TradeSide,
+};
+use fame_client::quote::{PoolReport, Quote};
+use fame_client::{instructions, pda};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+
+#[tokio::test]
+async fn initialize_sets_admin_and_default_fees() {
+    let mut env = Env::new().await;
+
+    let global_state: GlobalState = env.account(&pda::global_state().0).await;
+    assert_eq!(global_state.admin, env.admin.pubkey());
+    assert!(!global_state.paused);
+    assert_eq!(global_state.buy_fee_bps, GlobalState::DEFAULT_FEE_BPS);
+    assert_eq!(global_state.sell_fee_bps, GlobalState::DEFAULT_FEE_BPS);
+}
+
+#[tokio::test]
+async fn create_token_initializes_curve_accounts() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+
+    let token_info: TokenInfo = env.account(&pda::token_info(&mint).0).await;
+    assert_eq!(token_info.mint, mint);
+    assert_eq!(token_info.name, "Fame");
+    assert_eq!(token_info.symbol, "FAME");
+    assert_eq!(token_info.total_supply, 0);
+    assert_eq!(token_info.authority, creator.pubkey());
+
+    let bonding_curve: BondingCurve = env.account(&pda::bonding_curve(&mint).0).await;
+    assert_eq!(bonding_curve.token, mint);
+    assert_eq!(bonding_curve.admin, creator.pubkey());
+
+    let liquidity_pool: LiquidityPool = env.account(&pda::liquidity_pool(&mint).0).await;
+    assert_eq!(liquidity_pool.token, mint);
+    assert_eq!(liquidity_pool.balance, 0);
+    assert_eq!(liquidity_pool.accumulated_fees, 0);
+
+    let observations: PriceObservations = env.account(&pda::price_observations(&mint).0).await;
+    assert_eq!(observations.token, mint);
+    assert_eq!(observations.count, 1);
+
+    // Only the curve can mint
+    let mint_account: Mint = env.account(&mint).await;
+    assert_eq!(
+        Option::<Pubkey>::from(mint_account.mint_authority),
+        Some(pda::token_info(&mint).0)
+    );
+    assert_eq!(mint_account.supply, 0);
+
+    let portfolio: UserPortfolio = env
+        .account(&pda::user_portfolio(&creator.pubkey(), &mint).0)
+        .await;
+    assert_eq!(portfolio.user, creator.pubkey());
+    assert_eq!(portfolio.balance, 0);
+}
+
+#[tokio::test]
+async fn create_token_bundles_initial_buy() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let create = |mint: &Keypair, initial_buy_sol| {
+        let args = CreateTokenArgs {
+            name: "Fame".to_string(),
+            symbol: "FAME".to_string(),
+            initial_buy_sol,
+            ..CreateTokenArgs::default()
+        };
+        instructions::create_token(&creator.pubkey(), &mint.pubkey(), args)
+    };
+
+    let mint = Keypair::new();
+    let result = env
+        .send(&[create(&mint, Some(0))], &[&creator, &mint])
+        .await;
+    assert_error(result, ErrorCode::InvalidMintAmount);
+
+    env.send(&[create(&mint, Some(LAMPORTS_PER_SOL))], &[&creator, &mint])
+        .await
+        .unwrap();
+    let mint = mint.pubkey();
+    let global_state: GlobalState = env.account(&pda::global_state().0).await;
+    let bonding_curve: BondingCurve = env.account(&pda::bonding_curve(&mint).0).await;
+    let fee = global_state.buy_fee(LAMPORTS_PER_SOL);
+    let expected = calculate_tokens_to_mint(&bonding_curve, 0, LAMPORTS_PER_SOL - fee).unwrap();
+    assert!(expected > 0);
+    assert_eq!(env.token_balance(&creator.pubkey(), &mint).await, expected);
+
+    let token_info: TokenInfo = env.account(&pda::token_info(&mint).0).await;
+    assert_eq!(token_info.total_supply, expected);
+    let portfolio: UserPortfolio = env
+        .account(&pda::user_portfolio(&creator.pubkey(), &mint).0)
+        .await;
+    assert_eq!(portfolio.balance, expected);
+    let liquidity_pool: LiquidityPool = env.account(&pda::liquidity_pool(&mint).0).await;
+    assert_eq!(liquidity_pool.balance, LAMPORTS_PER_SOL - fee);
+    assert_eq!(liquidity_pool.accumulated_fees, fee);
+
+    // The bundled buy is a trade like any other, but creating without one is not
+    let admin = env.admin.insecure_clone();
+    env.send(
+        &[instructions::set_paused(&admin.pubkey(), true)],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+    let mint = Keypair::new();
+    let result = env
+        .send(&[create(&mint, Some(LAMPORTS_PER_SOL))], &[&creator, &mint])
+        .await;
+    assert_error(result, ErrorCode::TradingPaused);
+    env.send(&[create(&mint, None)], &[&creator, &mint])
+        .await
+        .unwrap();
+}
+
+fn create_with_allocation(
+    creator: &Keypair,
+    mint: &Keypair,
+    allocation: CreatorAllocation,
+) -> Instruction {
+    let args = CreateTokenArgs {
+        name: "Fame".to_string(),
+        symbol: "FAME".to_string(),
+        creator_allocation: Some(allocation),
+        ..CreateTokenArgs::default()
+    };
+    instructions::create_token(&creator.pubkey(), &mint.pubkey(), args)
+}
+
+#[tokio::test]
+async fn create_token_locks_creator_allocation() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    env.fund(&creator.pubkey(), 100 * LAMPORTS_PER_SOL).await;
+    let mint = Keypair::new();
+    let allocation = CreatorAllocation {
+        supply_bps: 500,
+        cliff_seconds: 100,
+        duration_seconds: 1_000,
+    };
+    let instruction = create_with_allocation(&creator, &mint, allocation);
+    env.send(&[instruction], &[&creator, &mint]).await.unwrap();
+    let mint = mint.pubkey();
+
+    let allocated = BondingCurve::DEFAULT_MAX_SUPPLY / 20;
+    let vesting: Vesting = env.account(&pda::vesting(&mint).0).await;
+    assert_eq!(vesting.beneficiary, creator.pubkey());
+    assert_eq!(vesting.total_amount, allocated);
+    assert_eq!(vesting.claimed_amount, 0);
+    assert_eq!(
+        env.token_balance(&pda::vesting(&mint).0, &mint).await,
+        allocated
+    );
+    let bonding_curve: BondingCurve = env.account(&pda::bonding_curve(&mint).0).await;
+    assert_eq!(bonding_curve.allocated_supply, allocated);
+    let token_info: TokenInfo = env.account(&pda::token_info(&mint).0).await;
+    assert_eq!(token_info.total_supply, allocated);
+    // The creator paid the curve price for the allocation
+    let liquidity_pool: LiquidityPool = env.account(&pda::liquidity_pool(&mint).0).await;
+    assert_eq!(
+        liquidity_pool.balance,
+        calculate_sol_to_return(&bonding_curve, allocated, allocated).unwrap()
+    );
+
+    // Buyers start above the allocation
+    let quoter = env.quoter(&mint).await;
+    assert_eq!(
+        quoter.spot_price().unwrap(),
+        bonding_curve.calculate_price(allocated).unwrap()
+    );
+    let user = env.user().await;
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    let data = env.view(instructions::verify_pool(&mint)).await.unwrap();
+    assert!(PoolReport::try_from_slice(&data).unwrap().solvent);
+    let bought = env.token_balance(&user.pubkey(), &mint).await;
+    env.sell(&user, &mint, bought).await.unwrap();
+
+    // Nothing before the cliff, then linearly until the end
+    let claim = [instructions::claim_vested(&creator.pubkey(), &mint)];
+    let result = env.send(&claim, &[&creator]).await;
+    assert_error(result, ErrorCode::NothingToClaim);
+    let result = env
+        .send(
+            &[instructions::claim_vested(&user.pubkey(), &mint)],
+            &[&user],
+        )
+        .await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    env.advance_clock(500).await;
+    env.send(&claim, &[&creator]).await.unwrap();
+    let vesting: Vesting = env.account(&pda::vesting(&mint).0).await;
+    assert!(vesting.claimed_amount >= allocated / 2);
+    assert!(vesting.claimed_amount < allocated);
+    let claimed = env.token_balance(&creator.pubkey(), &mint).await;
+    assert_eq!(claimed, vesting.claimed_amount);
+    let portfolio: UserPortfolio = env
+        .account(&pda::user_portfolio(&creator.pubkey(), &mint).0)
+        .await;
+    assert_eq!(portfolio.balance, claimed);
+
+    env.advance_clock(1_000).await;
+    env.send(&claim, &[&creator]).await.unwrap();
+    assert_eq!(env.token_balance(&creator.pubkey(), &mint).await, allocated);
+    assert_eq!(env.token_balance(&pda::vesting(&mint).0, &mint).await, 0);
+
+    // With every buyer gone, the allocation sells back against what the creator paid for it
+    env.sell(&creator, &mint, allocated).await.unwrap();
+    let token_info: TokenInfo = env.account(&pda::token_info(&mint).0).await;
+    assert_eq!(token_info.total_supply, 0);
+    let data = env.view(instructions::verify_pool(&mint)).await.unwrap();
+    assert!(PoolReport::try_from_slice(&data).unwrap().solvent);
+}
+
+#[tokio::test]
+async fn selling_the_creator_allocation_leaves_buyers_their_reserves() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    env.fund(&creator.pubkey(), 200 * LAMPORTS_PER_SOL).await;
+    let mint = Keypair::new();
+    let allocation = CreatorAllocation {
+        supply_bps: Vesting::MAX_ALLOCATION_BPS,
+        cliff_seconds: 0,
+        duration_seconds: 1,
+    };
+    let instruction = create_with_allocation(&creator, &mint, allocation);
+    env.send(&[instruction], &[&creator, &mint]).await.unwrap();
+    let mint = mint.pubkey();
+
+    let mut buyers = Vec::new();
+    for _ in 0..3 {
+        let buyer = env.user().await;
+        env.buy(&buyer, &mint, 5 * LAMPORTS_PER_SOL).await.unwrap();
+        buyers.push(buyer);
+    }
+
+    // The creator claims everything and sells it at the top of the curve first
+    env.advance_clock(1).await;
+    let claim = [instructions::claim_vested(&creator.pubkey(), &mint)];
+    env.send(&claim, &[&creator]).await.unwrap();
+    let allocated = BondingCurve::DEFAULT_MAX_SUPPLY / 10;
+    assert_eq!(env.token_balance(&creator.pubkey(), &mint).await, allocated);
+    env.sell(&creator, &mint, allocated).await.unwrap();
+
+    for buyer in &buyers {
+        let bought = env.token_balance(&buyer.pubkey(), &mint).await;
+        env.sell(buyer, &mint, bought).await.unwrap();
+    }
+    let token_info: TokenInfo = env.account(&pda::token_info(&mint).0).await;
+    assert_eq!(token_info.total_supply, 0);
+    let data = env.view(instructions::verify_pool(&mint)).await.unwrap();
+    assert!(PoolReport::try_from_slice(&data).unwrap().solvent);
+}
+
+#[tokio::test]
+async fn create_token_validates_allocation() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = Keypair::new();
+    let create = |supply_bps, cliff_seconds, duration_seconds| {
+        create_with_allocation(
+            &creator,
+            &mint,
+            CreatorAllocation {
+                supply_bps,
+                cliff_seconds,
+                duration_seconds,
+            },
+        )
+    };
+
+    for (instruction, expected) in [
+        (create(0, 0, 100), ErrorCode::InvalidAllocation),
+        (
+            create(Vesting::MAX_ALLOCATION_BPS + 1, 0, 100),
+            ErrorCode::InvalidAllocation,
+        ),
+        (create(500, 0, 0), ErrorCode::InvalidVestingSchedule),
+        (create(500, -1, 100), ErrorCode::InvalidVestingSchedule),
+        (create(500, 101, 100), ErrorCode::InvalidVestingSchedule),
+    ] {
+        let result = env.send(&[instruction], &[&creator, &mint]).await;
+        assert_error(result, expected);
+    }
+
+    // An allocation needs the vesting accounts
+    let mut instruction = create(500, 0, 100);
+    let vesting = pda::vesting(&mint.pubkey()).0;
+    let vesting_token_account = pda::token_account(&vesting, &mint.pubkey());
+    for meta in &mut instruction.accounts {
+        if meta.pubkey == vesting || meta.pubkey == vesting_token_account {
+            meta.pubkey = fame::ID;
+            meta.is_writable = false;
+        }
+    }
+    let result = env.send(&[instruction], &[&creator, &mint]).await;
+    assert_error(result, ErrorCode::InvalidAllocation);
+}
+
+#[tokio::test]
+async fn buys_wait_for_launch() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let token_info: TokenInfo = env.account(&pda::token_info(&mint).0).await;
+    assert!(token_info.launch_at <= env.now().await);
+
+    let launch_at = env.now().await + 3_600;
+    let args = CreateTokenArgs {
+        name: "Fame".to_string(),
+        symbol: "FAME".to_string(),
+        initial_buy_sol: Some(LAMPORTS_PER_SOL),
+        launch_at: Some(launch_at),
+        ..CreateTokenArgs::default()
+    };
+    // The creator's bundled buy goes ahead of the launch
+    let mint = env.create_token_with_args(&creator, args).await.unwrap();
+    let token_info: TokenInfo = env.account(&pda::token_info(&mint).0).await;
+    assert_eq!(token_info.launch_at, launch_at);
+    assert!(token_info.total_supply > 0);
+
+    let user = env.user().await;
+    assert_error(
+        env.buy(&user, &mint, LAMPORTS_PER_SOL).await,
+        ErrorCode::NotLaunched,
+    );
+    env.advance_clock(3_600).await;
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+}
+
+fn protected(launch_protection: LaunchProtection) -> CreateTokenArgs {
+    CreateTokenArgs {
+        name: "Fame".to_string(),
+        symbol: "FAME".to_string(),
+        launch_protection: Some(launch_protection),
+        ..CreateTokenArgs::default()
+    }
+}
+
+#[tokio::test]
+async fn launch_fee_decays_to_the_protocol_fee() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let args = protected(LaunchProtection {
+        start_fee_bps: 5_000,
+        duration_slots: 100,
+        burn_surplus: false,
+    });
+    let mint = env.create_token_with_args(&creator, args).await.unwrap();
+    let token_info: TokenInfo = env.account(&pda::token_info(&mint).0).await;
+    let pool = pda::liquidity_pool(&mint).0;
+    let user = env.user().await;
+    let account = instructions::create_token_account(&user.pubkey(), &user.pubkey(), &mint);
+    env.send(&[account], &[&user]).await.unwrap();
+
+    // Halfway through the window the fee is halfway down to the protocol fee of 100 bps
+    for (elapsed, fee_bps) in [(0, 5_000), (50, 2_550), (100, 100), (1_000, 100)] {
+        env.set_slot(token_info.launch_slot + elapsed).await;
+        let quote = env.quoter(&mint).await.quote(LAMPORTS_PER_SOL, 0).unwrap();
+        let buy = quote.buy.unwrap();
+        assert_eq!(buy.fee, fee_for(LAMPORTS_PER_SOL, fee_bps));
+
+        let before: LiquidityPool = env.account(&pool).await;
+        let lamports = env.lamports(&pool).await;
+        let held = env.token_balance(&user.pubkey(), &mint).await;
+        env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+        let minted = env.token_balance(&user.pubkey(), &mint).await - held;
+        let after: LiquidityPool = env.account(&pool).await;
+
+        // The surplus goes to the creator with the protocol fee
+        assert_eq!(minted, buy.amount_out);
+        assert_eq!(after.accumulated_fees - before.accumulated_fees, buy.fee);
+        assert_eq!(env.lamports(&pool).await - lamports, LAMPORTS_PER_SOL);
+    }
+}
+
+#[tokio::test]
+async fn launch_fee_surplus_can_be_burned() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mut args = protected(LaunchProtection {
+        start_fee_bps: 5_000,
+        duration_slots: 100,
+        burn_surplus: true,
+    });
+    args.launch_at = Some(env.now().await + 60);
+    let mint = env.create_token_with_args(&creator, args).await.unwrap();
+    let token_info: TokenInfo = env.account(&pda::token_info(&mint).0).await;
+    assert_eq!(token_info.launch_slot, 0);
+
+    // A scheduled launch opens the window with its first buy
+    env.advance_clock(60).await;
+    env.set_slot(1_000).await;
+    let pool = pda::liquidity_pool(&mint).0;
+    let lamports = env.lamports(&pool).await;
+    let user = env.user().await;
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+
+    let token_info: TokenInfo = env.account(&pda::token_info(&mint).0).await;
+    assert_eq!(token_info.launch_slot, 1_000);
+    let liquidity_pool: LiquidityPool = env.account(&pool).await;
+    let fee = fee_for(LAMPORTS_PER_SOL, GlobalState::DEFAULT_FEE_BPS);
+    let surplus = fee_for(LAMPORTS_PER_SOL, 5_000) - fee;
+    assert_eq!(liquidity_pool.accumulated_fees, fee);
+    assert_eq!(
+        env.lamports(&pool).await - lamports,
+        LAMPORTS_PER_SOL - surplus
+    );
+}
+
+#[tokio::test]
+async fn scheduled_launches_quote_the_fee_their_first_buy_pays() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mut args = protected(LaunchProtection {
+        start_fee_bps: 5_000,
+        duration_slots: 100,
+        burn_surplus: false,
+    });
+    args.launch_at = Some(env.now().await + 60);
+    let mint = env.create_token_with_args(&creator, args).await.unwrap();
+
+    // Long past the window's length, but the window only opens with the first buy
+    env.advance_clock(60).await;
+    env.set_slot(1_000).await;
+    let data = env
+        .view(instructions::get_quote(&mint, LAMPORTS_PER_SOL, 0))
+        .await
+        .unwrap();
+    let buy = Quote::try_from_slice(&data).unwrap().buy.unwrap();
+    assert_eq!(buy.fee, fee_for(LAMPORTS_PER_SOL, 5_000));
+    let quote = env.quoter(&mint).await.quote(LAMPORTS_PER_SOL, 0).unwrap();
+    assert_eq!(quote.buy, Some(buy));
+    let swap_quote = env
+        .amm(&mint)
+        .await
+        .quote(&QuoteParams {
+            amount: LAMPORTS_PER_SOL,
+            input_mint: native_mint::ID,
+            output_mint: mint,
+        })
+        .unwrap();
+    assert_eq!(
+        (swap_quote.out_amount, swap_quote.fee_amount),
+        (buy.amount_out, buy.fee)
+    );
+
+    let pool = pda::liquidity_pool(&mint).0;
+    let before: LiquidityPool = env.account(&pool).await;
+    let user = env.user().await;
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    let after: LiquidityPool = env.account(&pool).await;
+    assert_eq!(
+        env.token_balance(&user.pubkey(), &mint).await,
+        buy.amount_out
+    );
+    assert_eq!(after.accumulated_fees - before.accumulated_fees, buy.fee);
+}
+
+#[tokio::test]
+async fn create_token_caps_launch_protection() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+
+    for (start_fee_bps, duration_slots) in [
+        (LaunchProtection::MAX_START_FEE_BPS + 1, 100),
+        (5_000, 0),
+        (5_000, LaunchProtection::MAX_DURATION_SLOTS + 1),
+    ] {
+        let args = protected(LaunchProtection {
+            start_fee_bps,
+            duration_slots,
+            burn_surplus: false,
+        });
+        assert_error(
+            env.create_token_with_args(&creator, args).await,
+            ErrorCode::InvalidLaunchProtection,
+        );
+    }
+}
+
+fn limited(holding_limit: HoldingLimit) -> CreateTokenArgs {
+    CreateTokenArgs {
+        name: "Fame".to_string(),
+        symbol: "FAME".to_string(),
+        holding_limit: Some(holding_limit),
+        ..CreateTokenArgs::default()
+    }
+}
+
+#[tokio::test]
+async fn holding_limit_caps_early_buys() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    // About 99 tokens a SOL at the bottom of the curve
+    let args = limited(HoldingLimit {
+        cap: HoldingCap::Amount(150),
+        until_supply: Some(250),
+        until_timestamp: None,
+    });
+    let mint = env.create_token_with_args(&creator, args).await.unwrap();
+
+    let whale = env.user().await;
+    env.buy(&whale, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    assert_error(
+        env.buy(&whale, &mint, LAMPORTS_PER_SOL).await,
+        ErrorCode::HoldingLimitExceeded,
+    );
+
+    // Other wallets still buy, and the limit lifts with the supply
+    for _ in 0..2 {
+        let user = env.user().await;
+        env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    }
+    let token_info: TokenInfo = env.account(&pda::token_info(&mint).0).await;
+    assert!(token_info.total_supply >= 250);
+    env.buy(&whale, &mint, LAMPORTS_PER_SOL).await.unwrap();
+}
+
+#[tokio::test]
+async fn holding_limit_lifts_at_its_time() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    // 0.1% of the max supply, 100 tokens
+    let args = limited(HoldingLimit {
+        cap: HoldingCap::MaxSupplyBps(10),
+        until_supply: None,
+        until_timestamp: Some(env.now().await + 60),
+    });
+    let mint = env.create_token_with_args(&creator, args).await.unwrap();
+
+    let user = env.user().await;
+    assert_error(
+        env.buy(&user, &mint, 2 * LAMPORTS_PER_SOL).await,
+        ErrorCode::HoldingLimitExceeded,
+    );
+    env.advance_clock(60).await;
+    env.buy(&user, &mint, 2 * LAMPORTS_PER_SOL).await.unwrap();
+}
+
+#[tokio::test]
+async fn holding_limit_caps_the_initial_buy() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    // About 99 tokens a SOL at the bottom of the curve
+    let mut args = limited(HoldingLimit {
+        cap: HoldingCap::Amount(150),
+        until_supply: Some(1_000),
+        until_timestamp: None,
+    });
+    args.initial_buy_sol = Some(2 * LAMPORTS_PER_SOL);
+    assert_error(
+        env.create_token_with_args(&creator, args.clone()).await,
+        ErrorCode::HoldingLimitExceeded,
+    );
+
+    args.initial_buy_sol = Some(LAMPORTS_PER_SOL);
+    let mint = env.create_token_with_args(&creator, args).await.unwrap();
+    assert_error(
+        env.buy(&creator, &mint, LAMPORTS_PER_SOL).await,
+        ErrorCode::HoldingLimitExceeded,
+    );
+}
+
+#[tokio::test]
+async fn create_token_validates_holding_limit() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+
+    for (cap, until_supply) in [
+        (HoldingCap::MaxSupplyBps(0), Some(1_000)),
+        (HoldingCap::MaxSupplyBps(10_001), Some(1_000)),
+        (HoldingCap::Amount(0), Some(1_000)),
+        (HoldingCap::Amount(100), None),
+    ] {
+        let args = limited(HoldingLimit {
+            cap,
+            until_supply,
+            until_timestamp: None,
+        });
+        assert_error(
+            env.create_token_with_args(&creator, args).await,
+            ErrorCode::InvalidHoldingLimit,
+        );
+    }
+}
+
+#[tokio::test]
+async fn presale_admits_allowlisted_wallets() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mut listed = Vec::new();
+    for _ in 0..3 {
+        listed.push(env.user().await);
+    }
+    let allowlist = Allowlist::new(&listed.iter().map(Signer::pubkey).collect::<Vec<_>>());
+    let args = CreateTokenArgs {
+        name: "Fame".to_string(),
+        symbol: "FAME".to_string(),
+        presale: Some(Presale {
+            merkle_root: allowlist.root(),
+            max_per_wallet: 150,
+            ends_at: env.now().await + 60,
+        }),
+        ..CreateTokenArgs::default()
+    };
+    let mint = env.create_token_with_args(&creator, args).await.unwrap();
+    let outsider = env.user().await;
+    for user in [&outsider, &listed[2]] {
+        let account = instructions::create_token_account(&user.pubkey(), &user.pubkey(), &mint);
+        env.send(&[account], &[user]).await.unwrap();
+    }
+
+    let buy = |user: &Keypair, proof: Vec<[u8; 32]>| {
+        instructions::mint_token_with_proof(&user.pubkey(), &mint, LAMPORTS_PER_SOL, proof)
+    };
+    assert_error(
+        env.send(&[buy(&outsider, Vec::new())], &[&outsider]).await,
+        ErrorCode::NotAllowlisted,
+    );
+    let proof = allowlist.proof(&listed[0].pubkey()).unwrap();
+    assert_error(
+        env.send(&[buy(&outsider, proof)], &[&outsider]).await,
+        ErrorCode::NotAllowlisted,
+    );
+
+    // About 99 tokens a SOL, so the second buy passes the cap of 150
+    let user = &listed[2];
+    let proof = allowlist.proof(&user.pubkey()).unwrap();
+    env.send(&[buy(user, proof.clone())], &[user])
+        .await
+        .unwrap();
+    assert_error(
+        env.send(&[buy(user, proof)], &[user]).await,
+        ErrorCode::PresaleCapExceeded,
+    );
+
+    // An allowlisted wallet may pay from wrapped SOL
+    let payer = &listed[1];
+    let wsol = pda::token_account(&payer.pubkey(), &native_mint::ID);
+    env.send(
+        &[
+            instructions::create_token_account(&payer.pubkey(), &payer.pubkey(), &native_mint::ID),
+            system_instruction::transfer(&payer.pubkey(), &wsol, LAMPORTS_PER_SOL),
+            spl_token::instruction::sync_native(&spl_token::ID, &wsol).unwrap(),
+        ],
+        &[payer],
+    )
+    .await
+    .unwrap();
+    let expected = env.quoter(&mint).await.buy(LAMPORTS_PER_SOL).unwrap();
+    let proof = allowlist.proof(&payer.pubkey()).unwrap();
+    let instruction =
+        instructions::mint_token_from_wsol(&payer.pubkey(), &mint, LAMPORTS_PER_SOL, &wsol, proof);
+    env.send(&[instruction], &[payer]).await.unwrap();
+    assert_eq!(env.token_balance(&payer.pubkey(), &mint).await, expected);
+
+    // Public trading opens when the presale ends
+    env.advance_clock(60).await;
+    env.buy(&outsider, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    env.buy(user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+}
+
+#[tokio::test]
+async fn create_token_validates_presale() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let now = env.now().await;
+
+    for (max_per_wallet, ends_at) in [(100, now), (0, now + 60)] {
+        let args = CreateTokenArgs {
+            name: "Fame".to_string(),
+            symbol: "FAME".to_string(),
+            presale: Some(Presale {
+                merkle_root: [1; 32],
+                max_per_wallet,
+                ends_at,
+            }),
+            ..CreateTokenArgs::default()
+        };
+        assert_error(
+            env.create_token_with_args(&creator, args).await,
+            ErrorCode::InvalidPresale,
+        );
+    }
+}
+
+#[tokio::test]
+async fn buys_credit_a_separate_recipient() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let payer = env.user().await;
+    // A wallet that has never held anything
+    let recipient = Pubkey::new_unique();
+
+    let instruction = instructions::mint_token_for(
+        &payer.pubkey(),
+        &recipient,
+        &mint,
+        LAMPORTS_PER_SOL,
+        Vec::new(),
+    );
+    env.send(&[instruction], &[&payer]).await.unwrap();
+
+    let balance = env.token_balance(&recipient, &mint).await;
+    assert!(balance > 0);
+    let portfolio: UserPortfolio = env.account(&pda::user_portfolio(&recipient, &mint).0).await;
+    assert_eq!(portfolio.user, recipient);
+    assert_eq!(portfolio.balance, balance);
+    let payer_portfolio = pda::user_portfolio(&payer.pubkey(), &mint).0;
+    assert!(env
+        .try_account::<UserPortfolio>(&payer_portfolio)
+        .await
+        .is_none());
+}
+
+#[tokio::test]
+async fn burn_all_sells_the_whole_balance() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+
+    // Without closing, the emptied accounts stay and a second sell-all has nothing to sell
+    let instruction = instructions::burn_all(&user.pubkey(), &mint, false);
+    env.send(&[instruction], &[&user]).await.unwrap();
+    assert_eq!(env.token_balance(&user.pubkey(), &mint).await, 0);
+    let portfolio: UserPortfolio = env
+        .account(&pda::user_portfolio(&user.pubkey(), &mint).0)
+        .await;
+    assert_eq!(portfolio.balance, 0);
+    let token_info: TokenInfo = env.account(&pda::token_info(&mint).0).await;
+    assert_eq!(token_info.total_supply, 0);
+    assert_error(
+        env.send(
+            &[instructions::burn_all(&user.pubkey(), &mint, false)],
+            &[&user],
+        )
+        .await,
+        ErrorCode::InvalidBurnAmount,
+    );
+
+    // Closing refunds the rent of both accounts, with or without a balance left
+    for amount_sol in [0, LAMPORTS_PER_SOL] {
+        if amount_sol > 0 {
+            env.buy(&user, &mint, amount_sol).await.unwrap();
+        }
+        let token_account = pda::token_account(&user.pubkey(), &mint);
+        let portfolio = pda::user_portfolio(&user.pubkey(), &mint).0;
+        let rent = env.lamports(&token_account).await + env.lamports(&portfolio).await;
+        let before = env.lamports(&user.pubkey()).await;
+        let instruction = instructions::burn_all(&user.pubkey(), &mint, true);
+        env.send(&[instruction], &[&user]).await.unwrap();
+
+        assert!(env.try_account::<UserPortfolio>(&portfolio).await.is_none());
+        assert_eq!(env.lamports(&token_account).await, 0);
+        // Less the transaction fee
+        assert!(env.lamports(&user.pubkey()).await + 10_000 > before + rent);
+    }
+}
+
+#[tokio::test]
+async fn burn_all_keeps_a_portfolio_that_still_counts_tokens() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    let bought = env.token_balance(&user.pubkey(), &mint).await;
+
+    // Tokens moved out of the account are still counted by the portfolio
+    let other = env.user().await;
+    let token_account = pda::token_account(&user.pubkey(), &mint);
+    env.send(
+        &[
+            instructions::create_token_account(&user.pubkey(), &other.pubkey(), &mint),
+            spl_token::instruction::transfer(
+                &spl_token::ID,
+                &token_account,
+                &pda::token_account(&other.pubkey(), &mint),
+                &user.pubkey(),
+                &[],
+                bought,
+            )
+            .unwrap(),
+        ],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    let instruction = instructions::burn_all(&user.pubkey(), &mint, true);
+    assert_error(
+        env.send(&[instruction], &[&user]).await,
+        ErrorCode::PortfolioNotEmpty,
+    );
+    let portfolio: UserPortfolio = env
+        .account(&pda::user_portfolio(&user.pubkey(), &mint).0)
+        .await;
+    assert_eq!(portfolio.balance, bought);
+}
+
+#[tokio::test]
+async fn batch_trade_rebalances_across_tokens() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let sold = env.create_token(&creator).await.unwrap();
+    let bought = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+    env.buy(&user, &sold, LAMPORTS_PER_SOL).await.unwrap();
+    let held = env.token_balance(&user.pubkey(), &sold).await;
+
+    let sell_quote = env.quoter(&sold).await.quote(0, held / 2).unwrap();
+    let sell_quote = sell_quote.sell.unwrap();
+    let buy_quote = env
+        .quoter(&bought)
+        .await
+        .quote(LAMPORTS_PER_SOL, 0)
+        .unwrap();
+    let buy_quote = buy_quote.buy.unwrap();
+    let legs = vec![
+        TradeLeg {
+            mint: sold,
+            side: TradeSide::Sell,
+            amount: held / 2,
+            limit: sell_quote.amount_out,
+        },
+        TradeLeg {
+            mint: bought,
+            side: TradeSide::Buy,
+            amount: LAMPORTS_PER_SOL,
+            limit: buy_quote.amount_out,
+        },
+    ];
+    // The first buy of a token creates the portfolio, but not the token account
+    let instructions = [
+        instructions::create_token_account(&user.pubkey(), &user.pubkey(), &bought),
+        instructions::batch_trade(&user.pubkey(), legs),
+    ];
+    env.send(&instructions, &[&user]).await.unwrap();
+
+    assert_eq!(
+        env.token_balance(&user.pubkey(), &sold).await,
+        held - held / 2
+    );
+    assert_eq!(
+        env.token_balance(&user.pubkey(), &bought).await,
+        buy_quote.amount_out
+    );
+    let portfolio: UserPortfolio = env
+        .account(&pda::user_portfolio(&user.pubkey(), &bought).0)
+        .await;
+    assert_eq!(portfolio.user, user.pubkey());
+    assert_eq!(portfolio.balance, buy_quote.amount_out);
+}
+
+#[tokio::test]
+async fn batch_trade_fails_as_a_whole() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let first = env.create_token(&creator).await.unwrap();
+    let second = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+    env.buy(&user, &first, 1).await.unwrap();
+    env.buy(&user, &second, 1).await.unwrap();
+
+    let leg = |mint: Pubkey, limit: u64| TradeLeg {
+        mint,
+        side: TradeSide::Buy,
+        amount: LAMPORTS_PER_SOL,
+        limit,
+    };
+    let result = env
+        .send(
+            &[instructions::batch_trade(
+                &user.pubkey(),
+                vec![leg(first, 0), leg(second, u64::MAX)],
+            )],
+            &[&user],
+        )
+        .await;
+    assert_error(result, ErrorCode::SlippageExceeded);
+    let token_info: TokenInfo = env.account(&pda::token_info(&first).0).await;
+    assert_eq!(token_info.total_supply, 0);
+
+    // Legs must come with exactly their accounts, and name the mint they trade
+    let mut instruction = instructions::batch_trade(&user.pubkey(), vec![leg(first, 0)]);
+    instruction.accounts.pop();
+    assert_error(
+        env.send(&[instruction], &[&user]).await,
+        ErrorCode::InvalidBatch,
+    );
+    assert_error(
+        env.send(
+            &[instructions::batch_trade(&user.pubkey(), Vec::new())],
+            &[&user],
+        )
+        .await,
+        ErrorCode::InvalidBatch,
+    );
+    let mut instruction = instructions::batch_trade(&user.pubkey(), vec![leg(first, 0)]);
+    let data = instructions::batch_trade(&user.pubkey(), vec![leg(second, 0)]).data;
+    instruction.data = data;
+    assert_error(
+        env.send(&[instruction], &[&user]).await,
+        ErrorCode::InvalidToken,
+    );
+}
+
+#[tokio::test]
+async fn batch_trade_creates_a_prefunded_portfolio() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let rent: Rent = env.context.banks_client.get_sysvar().await.unwrap();
+    let (least, rent) = (
+        rent.minimum_balance(0),
+        rent.minimum_balance(UserPortfolio::LEN),
+    );
+
+    // Lamports sent to the portfolio address ahead of the first buy do not block it, whether
+    // they fall short of the rent or cover it
+    for prefunded in [least, 2 * rent] {
+        let user = env.user().await;
+        let portfolio = pda::user_portfolio(&user.pubkey(), &mint).0;
+        env.fund(&portfolio, prefunded).await;
+
+        let leg = TradeLeg {
+            mint,
+            side: TradeSide::Buy,
+            amount: LAMPORTS_PER_SOL,
+            limit: 1,
+        };
+        let instructions = [
+            instructions::create_token_account(&user.pubkey(), &user.pubkey(), &mint),
+            instructions::batch_trade(&user.pubkey(), vec![leg]),
+        ];
+        env.send(&instructions, &[&user]).await.unwrap();
+
+        let user_portfolio: UserPortfolio = env.account(&portfolio).await;
+        assert_eq!(user_portfolio.user, user.pubkey());
+        assert_eq!(
+            user_portfolio.balance,
+            env.token_balance(&user.pubkey(), &mint).await
+        );
+        assert_eq!(env.lamports(&portfolio).await, prefunded.max(rent));
+    }
+}
+
+#[tokio::test]
+async fn swap_trades_at_the_amm_quote() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+    let sol = native_mint::ID;
+
+    let amm = env.amm(&mint).await;
+    assert_eq!(amm.key(), pda::liquidity_pool(&mint).0);
+    assert_eq!(amm.reserve_mints(), vec![sol, mint]);
+    let quote = amm
+        .quote(&QuoteParams {
+            amount: LAMPORTS_PER_SOL,
+            input_mint: sol,
+            output_mint: mint,
+        })
+        .unwrap();
+    assert_eq!(
+        quote.fee_amount,
+        fee_for(LAMPORTS_PER_SOL, GlobalState::DEFAULT_FEE_BPS)
+    );
+    let swap = |in_amount, minimum_out_amount, source_mint, destination_mint| {
+        let swap = amm
+            .swap_and_account_metas(&SwapParams {
+                user: user.pubkey(),
+                source_mint,
+                destination_mint,
+                in_amount,
+                minimum_out_amount,
+            })
+            .unwrap();
+        Instruction {
+            program_id: swap.program_id,
+            accounts: swap.account_metas,
+            data: swap.data,
+        }
+    };
+
+    // A buy short of its minimum fails, and one at the quote creates the user's accounts
+    assert_error(
+        env.send(
+            &[swap(LAMPORTS_PER_SOL, quote.out_amount + 1, sol, mint)],
+            &[&user],
+        )
+        .await,
+        ErrorCode::SlippageExceeded,
+    );
+    env.send(
+        &[swap(LAMPORTS_PER_SOL, quote.out_amount, sol, mint)],
+        &[&user],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        env.token_balance(&user.pubkey(), &mint).await,
+        quote.out_amount
+    );
+
+    let amm = env.amm(&mint).await;
+    let sold = quote.out_amount / 2;
+    let quote = amm
+        .quote(&QuoteParams {
+            amount: sold,
+            input_mint: mint,
+            output_mint: sol,
+        })
+        .unwrap();
+    let lamports = env.lamports(&user.pubkey()).await;
+    env.send(&[swap(sold, quote.out_amount, mint, sol)], &[&user])
+        .await
+        .unwrap();
+    // The user also pays the transaction fee
+    assert_eq!(
+        env.lamports(&user.pubkey()).await,
+        lamports + quote.out_amount - 5_000
+    );
+    assert_eq!(
+        env.token_balance(&user.pubkey(), &mint).await,
+        quote.in_amount
+    );
+
+    // The market only trades its token against lamports
+    let other = env.create_token(&creator).await.unwrap();
+    assert!(amm
+        .quote(&QuoteParams {
+            amount: 1,
+            input_mint: sol,
+            output_mint: other,
+        })
+        .is_err());
+}
+
+#[tokio::test]
+async fn wrapped_sol_pays_buys_and_receives_sells() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+    let wsol = pda::token_account(&user.pubkey(), &native_mint::ID);
+    env.send(
+        &[
+            instructions::create_token_account(&user.pubkey(), &user.pubkey(), &native_mint::ID),
+            system_instruction::transfer(&user.pubkey(), &wsol, 2 * LAMPORTS_PER_SOL),
+            spl_token::instruction::sync_native(&spl_token::ID, &wsol).unwrap(),
+        ],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    let expected = env.quoter(&mint).await.buy(LAMPORTS_PER_SOL).unwrap();
+    env.send(
+        &[instructions::mint_token_from_wsol(
+            &user.pubkey(),
+            &mint,
+            LAMPORTS_PER_SOL,
+            &wsol,
+            Vec::new(),
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+    assert_eq!(env.token_balance(&user.pubkey(), &mint).await, expected);
+    assert_eq!(
+        env.token_balance(&user.pubkey(), &native_mint::ID).await,
+        LAMPORTS_PER_SOL
+    );
+    // The unwrap account is gone again
+    assert_eq!(env.lamports(&pda::wsol_unwrap(&user.pubkey()).0).await, 0);
+
+    let sold = expected / 2;
+    let quoter = env.quoter(&mint).await;
+    let sol_to_return = quoter.sell(sold).unwrap();
+    let payout = sol_to_return - quoter.global_state.sell_fee(sol_to_return);
+    let lamports = env.lamports(&user.pubkey()).await;
+    env.send(
+        &[instructions::burn_token_to_wsol(
+            &user.pubkey(),
+            &mint,
+            sold,
+            &wsol,
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        env.token_balance(&user.pubkey(), &native_mint::ID).await,
+        LAMPORTS_PER_SOL + payout
+    );
+    // Only the transaction fee came out of the user's lamports
+    assert_eq!(env.lamports(&user.pubkey()).await, lamports - 5_000);
+
+    // Payment takes all the wrapped SOL accounts, and proceeds only go to a wrapped SOL account
+    let mut instruction = instructions::mint_token_from_wsol(
+        &user.pubkey(),
+        &mint,
+        LAMPORTS_PER_SOL,
+        &wsol,
+        Vec::new(),
+    );
+    for account in instruction.accounts.iter_mut() {
+        if account.pubkey == pda::wsol_unwrap(&user.pubkey()).0 {
+            *account = AccountMeta::new_readonly(fame::ID, false);
+        }
+    }
+    assert_error(
+        env.send(&[instruction], &[&user]).await,
+        ErrorCode::InvalidWsolAccount,
+    );
+    let token_account = pda::token_account(&user.pubkey(), &mint);
+    assert_error(
+        env.send(
+            &[instructions::burn_token_to_wsol(
+                &user.pubkey(),
+                &mint,
+                1,
+                &token_account,
+            )],
+            &[&user],
+        )
+        .await,
+        ErrorCode::InvalidWsolAccount,
+    );
+}
+
+#[tokio::test]
+async fn signed_intents_trade_for_the_user_once() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+    let relayer = env.user().await;
+    let wsol = pda::token_account(&user.pubkey(), &native_mint::ID);
+    env.send(
+        &[
+            instructions::create_token_account(&user.pubkey(), &user.pubkey(), &native_mint::ID),
+            system_instruction::transfer(&user.pubkey(), &wsol, 2 * LAMPORTS_PER_SOL),
+            spl_token::instruction::sync_native(&spl_token::ID, &wsol).unwrap(),
+            instructions::approve_intent_delegate(&user.pubkey(), &wsol, LAMPORTS_PER_SOL),
+        ],
+        &[&user],
+    )
+    .await
+    .unwrap();
+    let expiry = env.now().await + 60;
+    let signed = |intent: TradeIntent| {
+        let signature = user.sign_message(&intent.message());
+        let signature: &[u8; 64] = signature.as_ref().try_into().unwrap();
+        instructions::intent_signature(&intent, signature)
+    };
+
+    let expected = env.quoter(&mint).await.buy(LAMPORTS_PER_SOL).unwrap();
+    let buy = TradeIntent {
+        user: user.pubkey(),
+        mint,
+        side: TradeSide::Buy,
+        amount: LAMPORTS_PER_SOL,
+        limit: expected,
+        nonce: 7,
+        expiry,
+    };
+    let lamports = env.lamports(&user.pubkey()).await;
+    let execute_buy = [
+        signed(buy),
+        instructions::execute_intent(&relayer.pubkey(), buy),
+    ];
+    env.send(&execute_buy, &[&relayer]).await.unwrap();
+    assert_eq!(env.token_balance(&user.pubkey(), &mint).await, expected);
+    assert_eq!(
+        env.token_balance(&user.pubkey(), &native_mint::ID).await,
+        LAMPORTS_PER_SOL
+    );
+    assert_eq!(env.lamports(&user.pubkey()).await, lamports);
+    // An intent executes once
+    assert_error(
+        env.send(&execute_buy, &[&relayer]).await,
+        ErrorCode::NonceUsed,
+    );
+
+    let token_account = pda::token_account(&user.pubkey(), &mint);
+    env.send(
+        &[instructions::approve_intent_delegate(
+            &user.pubkey(),
+            &token_account,
+            expected,
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+    let sold = expected / 2;
+    let quoter = env.quoter(&mint).await;
+    let sol_to_return = quoter.sell(sold).unwrap();
+    let payout = sol_to_return - quoter.global_state.sell_fee(sol_to_return);
+    let sell = TradeIntent {
+        side: TradeSide::Sell,
+        amount: sold,
+        limit: payout,
+        nonce: 3,
+        ..buy
+    };
+    let lamports = env.lamports(&user.pubkey()).await;
+    env.send(
+        &[
+            signed(sell),
+            instructions::execute_intent(&relayer.pubkey(), sell),
+        ],
+        &[&relayer],
+    )
+    .await
+    .unwrap();
+    assert_eq!(env.lamports(&user.pubkey()).await, lamports + payout);
+    assert_eq!(
+        env.token_balance(&user.pubkey(), &mint).await,
+        expected - sold
+    );
+
+    // Intents need the user's signature of exactly them, before they expire
+    let other = TradeIntent {
+        nonce: 4,
+        amount: 1,
+        limit: 0,
+        ..sell
+    };
+    let tampered = TradeIntent { amount: 2, ..other };
+    assert_error(
+        env.send(
+            &[
+                signed(other),
+                instructions::execute_intent(&relayer.pubkey(), tampered),
+            ],
+            &[&relayer],
+        )
+        .await,
+        ErrorCode::InvalidIntentSignature,
+    );
+    assert_error(
+        env.send(
+            &[instructions::execute_intent(&relayer.pubkey(), other)],
+            &[&relayer],
+        )
+        .await,
+        ErrorCode::InvalidIntentSignature,
+    );
+    env.advance_clock(61).await;
+    assert_error(
+        env.send(
+            &[
+                signed(other),
+                instructions::execute_intent(&relayer.pubkey(), other),
+            ],
+            &[&relayer],
+        )
+        .await,
+        ErrorCode::IntentExpired,
+    );
+}
+
+#[test]
+fn intent_nonces_slide_past_the_window() {
+    let mut nonces = IntentNonces {
+        user: Pubkey::new_unique(),
+        base: 0,
+        used: [0; 4],
+    };
+    nonces.spend(5).unwrap();
+    nonces.spend(255).unwrap();
+    assert_eq!(nonces.base, 0);
+    assert!(nonces.spend(5).is_err());
+
+    // Sliding up to 260 spends everything below 5 and keeps what was spent above it
+    nonces.spend(260).unwrap();
+    assert_eq!(nonces.base, 5);
+    assert!(nonces.spend(4).is_err());
+    assert!(nonces.spend(5).is_err());
+    assert!(nonces.spend(255).is_err());
+    nonces.spend(6).unwrap();
+
+    nonces.spend(10_000).unwrap();
+    assert_eq!(nonces.base, 10_000 - 255);
+    assert_eq!(nonces.used, [0, 0, 0, 1 << 63]);
+}
+
+#[tokio::test]
+async fn limit_orders_fill_once_the_price_reaches_their_trigger() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let whale = env.user().await;
+    let owner = env.user().await;
+    let keeper = env.user().await;
+    env.buy(&whale, &mint, 3 * LAMPORTS_PER_SOL).await.unwrap();
+    env.buy(&owner, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    let tokens = env.token_balance(&owner.pubkey(), &mint).await;
+    let spot_price = env.quoter(&mint).await.spot_price().unwrap();
+
+    // Buy once the price drops, sell once it rises
+    env.send(
+        &[
+            instructions::place_order(
+                &owner.pubkey(),
+                &mint,
+                1,
+                TradeSide::Buy,
+                LAMPORTS_PER_SOL,
+                spot_price - 1,
+                0,
+            ),
+            instructions::place_order(
+                &owner.pubkey(),
+                &mint,
+                2,
+                TradeSide::Sell,
+                tokens,
+                spot_price + 1,
+                0,
+            ),
+        ],
+        &[&owner],
+    )
+    .await
+    .unwrap();
+    assert_eq!(env.token_balance(&owner.pubkey(), &mint).await, 0);
+    let execute =
+        |id, side| instructions::execute_order(&keeper.pubkey(), &owner.pubkey(), &mint, id, side);
+    for (id, side) in [(1, TradeSide::Buy), (2, TradeSide::Sell)] {
+        assert_error(
+            env.send(&[execute(id, side)], &[&keeper]).await,
+            ErrorCode::OrderNotTriggered,
+        );
+    }
+
+    let whale_tokens = env.token_balance(&whale.pubkey(), &mint).await;
+    env.sell(&whale, &mint, whale_tokens / 2).await.unwrap();
+    let reward = fee_for(LAMPORTS_PER_SOL, Order::KEEPER_REWARD_BPS);
+    let expected = env
+        .quoter(&mint)
+        .await
+        .buy(LAMPORTS_PER_SOL - reward)
+        .unwrap();
+    let keeper_lamports = env.lamports(&keeper.pubkey()).await;
+    env.send(&[execute(1, TradeSide::Buy)], &[&keeper])
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(&owner.pubkey(), &mint).await, expected);
+    assert_eq!(
+        env.lamports(&keeper.pubkey()).await,
+        keeper_lamports + reward - 5_000
+    );
+    assert_eq!(
+        env.lamports(&pda::order(&owner.pubkey(), &mint, 1).0).await,
+        0
+    );
+
+    env.buy(&whale, &mint, 5 * LAMPORTS_PER_SOL).await.unwrap();
+    let quoter = env.quoter(&mint).await;
+    let sol_to_return = quoter.sell(tokens).unwrap();
+    let sol_out = sol_to_return - quoter.global_state.sell_fee(sol_to_return);
+    let reward = fee_for(sol_out, Order::KEEPER_REWARD_BPS);
+    let order = pda::order(&owner.pubkey(), &mint, 2).0;
+    let escrow = pda::token_account(&order, &mint);
+    let rent = env.lamports(&order).await + env.lamports(&escrow).await;
+    let owner_lamports = env.lamports(&owner.pubkey()).await;
+    let keeper_lamports = env.lamports(&keeper.pubkey()).await;
+    env.send(&[execute(2, TradeSide::Sell)], &[&keeper])
+        .await
+        .unwrap();
+    assert_eq!(
+        env.lamports(&owner.pubkey()).await,
+        owner_lamports + sol_out - reward + rent
+    );
+    assert_eq!(
+        env.lamports(&keeper.pubkey()).await,
+        keeper_lamports + reward - 5_000
+    );
+    assert_eq!(env.token_balance(&owner.pubkey(), &mint).await, expected);
+    let portfolio: UserPortfolio = env
+        .account(&pda::user_portfolio(&owner.pubkey(), &mint).0)
+        .await;
+    assert_eq!(portfolio.balance, expected);
+}
+
+#[tokio::test]
+async fn cancelled_orders_return_their_escrow() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let owner = env.user().await;
+    env.buy(&owner, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    let tokens = env.token_balance(&owner.pubkey(), &mint).await;
+
+    env.send(
+        &[
+            instructions::place_order(
+                &owner.pubkey(),
+                &mint,
+                1,
+                TradeSide::Buy,
+                LAMPORTS_PER_SOL,
+                1,
+                0,
+            ),
+            instructions::place_order(
+                &owner.pubkey(),
+                &mint,
+                2,
+                TradeSide::Sell,
+                tokens,
+                u64::MAX,
+                0,
+            ),
+        ],
+        &[&owner],
+    )
+    .await
+    .unwrap();
+    let order: Order = env.account(&pda::order(&owner.pubkey(), &mint, 1).0).await;
+    assert_eq!(
+        (order.owner, order.side, order.amount, order.trigger_price),
+        (owner.pubkey(), TradeSide::Buy, LAMPORTS_PER_SOL, 1)
+    );
+
+    // Only the owner cancels
+    let stranger = env.user().await;
+    let mut instruction = instructions::cancel_order(&owner.pubkey(), &mint, 1, TradeSide::Buy);
+    instruction.accounts[0].pubkey = stranger.pubkey();
+    assert_error(
+        env.send(&[instruction], &[&stranger]).await,
+        ErrorCode::Unauthorized,
+    );
+
+    let lamports = env.lamports(&owner.pubkey()).await;
+    let sell_order = pda::order(&owner.pubkey(), &mint, 2).0;
+    // The buy order holds its escrow besides its rent
+    let escrowed = env.lamports(&pda::order(&owner.pubkey(), &mint, 1).0).await
+        + env.lamports(&sell_order).await
+        + env.lamports(&pda::token_account(&sell_order, &mint)).await;
+    env.send(
+        &[
+            instructions::cancel_order(&owner.pubkey(), &mint, 1, TradeSide::Buy),
+            instructions::cancel_order(&owner.pubkey(), &mint, 2, TradeSide::Sell),
+        ],
+        &[&owner],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        env.lamports(&owner.pubkey()).await,
+        lamports + escrowed - 5_000
+    );
+    assert_eq!(env.token_balance(&owner.pubkey(), &mint).await, tokens);
+    assert_eq!(env.lamports(&sell_order).await, 0);
+
+    // An order escrows something, and a sell escrows it in its token account
+    assert_error(
+        env.send(
+            &[instructions::place_order(
+                &owner.pubkey(),
+                &mint,
+                3,
+                TradeSide::Buy,
+                0,
+                1,
+                0,
+            )],
+            &[&owner],
+        )
+        .await,
+        ErrorCode::InvalidOrder,
+    );
+    let mut instruction =
+        instructions::place_order(&owner.pubkey(), &mint, 3, TradeSide::Buy, 1, 1, 0);
+    instruction.data =
+        instructions::place_order(&owner.pubkey(), &mint, 3, TradeSide::Sell, 1, 1, 0).data;
+    assert_error(
+        env.send(&[instruction], &[&owner]).await,
+        ErrorCode::InvalidOrder,
+    );
+}
+
+#[tokio::test]
+async fn limit_orders_fail_past_their_slippage_limit() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let owner = env.user().await;
+    let keeper = env.user().await;
+    env.buy(&owner, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    let tokens = env.token_balance(&owner.pubkey(), &mint).await;
+
+    // Both orders trigger right away, but neither can meet its limit
+    env.send(
+        &[
+            instructions::place_order(
+                &owner.pubkey(),
+                &mint,
+                1,
+                TradeSide::Buy,
+                LAMPORTS_PER_SOL,
+                u64::MAX,
+                u64::MAX,
+            ),
+            instructions::place_order(
+                &owner.pubkey(),
+                &mint,
+                2,
+                TradeSide::Sell,
+                tokens,
+                1,
+                u64::MAX,
+            ),
+        ],
+        &[&owner],
+    )
+    .await
+    .unwrap();
+    for (id, side) in [(1, TradeSide::Buy), (2, TradeSide::Sell)] {
+        let instruction =
+            instructions::execute_order(&keeper.pubkey(), &owner.pubkey(), &mint, id, side);
+        assert_error(
+            env.send(&[instruction], &[&keeper]).await,
+            ErrorCode::SlippageExceeded,
+        );
+        let order: Order = env.account(&pda::order(&owner.pubkey(), &mint, id).0).await;
+        assert_eq!(order.side, side);
+    }
+}
+
+#[tokio::test]
+async fn portfolios_count_the_tokens_sell_orders_escrow() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let owner = env.user().await;
+    env.buy(&owner, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    let tokens = env.token_balance(&owner.pubkey(), &mint).await;
+    let portfolio = pda::user_portfolio(&owner.pubkey(), &mint).0;
+
+    // An order only escrows tokens the portfolio counts
+    let place = |id, amount| {
+        instructions::place_order(
+            &owner.pubkey(),
+            &mint,
+            id,
+            TradeSide::Sell,
+            amount,
+            u64::MAX,
+            0,
+        )
+    };
+    assert_error(
+        env.send(&[place(1, tokens + 1)], &[&owner]).await,
+        ErrorCode::InsufficientBalance,
+    );
+    env.send(&[place(1, tokens / 2)], &[&owner]).await.unwrap();
+    assert_eq!(
+        env.token_balance(&owner.pubkey(), &mint).await,
+        tokens - tokens / 2
+    );
+    let user_portfolio: UserPortfolio = env.account(&portfolio).await;
+    assert_eq!(user_portfolio.balance, tokens);
+
+    // So the portfolio outlives selling what is left in the token account
+    let burn_all = instructions::burn_all(&owner.pubkey(), &mint, true);
+    assert_error(
+        env.send(std::slice::from_ref(&burn_all), &[&owner]).await,
+        ErrorCode::PortfolioNotEmpty,
+    );
+
+    // Only the owner gets the escrow back, which the portfolio already counts
+    let stranger = env.user().await;
+    let mut instruction = instructions::cancel_order(&owner.pubkey(), &mint, 1, TradeSide::Sell);
+    instruction.accounts[0].pubkey = stranger.pubkey();
+    assert_error(
+        env.send(&[instruction], &[&stranger]).await,
+        ErrorCode::Unauthorized,
+    );
+    let cancel = instructions::cancel_order(&owner.pubkey(), &mint, 1, TradeSide::Sell);
+    env.send(&[cancel], &[&owner]).await.unwrap();
+    assert_eq!(env.token_balance(&owner.pubkey(), &mint).await, tokens);
+    let user_portfolio: UserPortfolio = env.account(&portfolio).await;
+    assert_eq!(user_portfolio.balance, tokens);
+
+    env.send(&[burn_all], &[&owner]).await.unwrap();
+    assert!(env.try_account::<UserPortfolio>(&portfolio).await.is_none());
+}
+
+#[tokio::test]
+async fn buys_stop_at_max_supply() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+
+    env.edit(
+        &pda::bonding_curve(&mint).0,
+        |bonding_curve: &mut BondingCurve| bonding_curve.max_supply = 10,
+    )
+    .await;
+    assert_error(
+        env.buy(&user, &mint, LAMPORTS_PER_SOL).await,
+        ErrorCode::MaxSupplyExceeded,
+    );
+    env.buy(&user, &mint, LAMPORTS_PER_SOL / 1_000)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn create_token_validates_metadata() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+
+    let result = env
+        .create_token_with(&creator, &"n".repeat(33), "FAME", "")
+        .await;
+    assert_error(result, ErrorCode::InvalidTokenName);
+
+    let result = env
+        .create_token_with(&creator, "Fame", &"S".repeat(11), "")
+        .await;
+    assert_error(result, ErrorCode::InvalidTokenSymbol);
+
+    let result = env
+        .create_token_with(&creator, "Fame", "FAME", &"u".repeat(201))
+        .await;
+    assert_error(result, ErrorCode::InvalidSocialAccountUrl);
+}
+
+#[tokio::test]
+async fn buy_mints_tokens_and_collects_fee() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+    let pool = pda::liquidity_pool(&mint).0;
+    let pool_lamports = env.lamports(&pool).await;
+
+    let expected = env.quoter(&mint).await.buy(LAMPORTS_PER_SOL).unwrap();
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+
+    assert!(expected > 0);
+    assert_eq!(env.token_balance(&user.pubkey(), &mint).await, expected);
+
+    let portfolio: UserPortfolio = env
+        .account(&pda::user_portfolio(&user.pubkey(), &mint).0)
+        .await;
+    assert_eq!(portfolio.user, user.pubkey());
+    assert_eq!(portfolio.token, mint);
+    assert_eq!(portfolio.balance, expected);
+
+    let token_info: TokenInfo = env.account(&pda::token_info(&mint).0).await;
+    assert_eq!(token_info.total_supply, expected);
+
+    let fee = LAMPORTS_PER_SOL / 100;
+    let liquidity_pool: LiquidityPool = env.account(&pool).await;
+    assert_eq!(liquidity_pool.balance, LAMPORTS_PER_SOL - fee);
+    assert_eq!(liquidity_pool.accumulated_fees, fee);
+    assert_eq!(env.lamports(&pool).await, pool_lamports + LAMPORTS_PER_SOL);
+
+    // A second buy reuses the portfolio and lands higher on the curve
+    let second = env.quoter(&mint).await.buy(LAMPORTS_PER_SOL).unwrap();
+    assert!(second < expected);
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    assert_eq!(
+        env.token_balance(&user.pubkey(), &mint).await,
+        expected + second
+    );
+}
+
+#[tokio::test]
+async fn sell_burns_tokens_and_returns_sol() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    let bought = env.token_balance(&user.pubkey(), &mint).await;
+
+    let amount = bought / 2;
+    let gross = env.quoter(&mint).await.sell(amount).unwrap();
+    let fee = gross / 100;
+    let user_lamports = env.lamports(&user.pubkey()).await;
+    let before: LiquidityPool = env.account(&pda::liquidity_pool(&mint).0).await;
+
+    env.sell(&user, &mint, amount).await.unwrap();
+
+    assert_eq!(
+        env.token_balance(&user.pubkey(), &mint).await,
+        bought - amount
+    );
+    // The seller also pays the transaction fee
+    let received = env.lamports(&user.pubkey()).await + 5_000 - user_lamports;
+    assert_eq!(received, gross - fee);
+
+    let after: LiquidityPool = env.account(&pda::liquidity_pool(&mint).0).await;
+    assert_eq!(after.balance, before.balance - gross);
+    assert_eq!(after.accumulated_fees, before.accumulated_fees + fee);
+
+    let token_info: TokenInfo = env.account(&pda::token_info(&mint).0).await;
+    assert_eq!(token_info.total_supply, bought - amount);
+    let mint_account: Mint = env.account(&mint).await;
+    assert_eq!(mint_account.supply, bought - amount);
+}
+
+#[tokio::test]
+async fn get_quote_matches_trades() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+
+    let data = env
+        .view(instructions::get_quote(&mint, LAMPORTS_PER_SOL, 10))
+        .await
+        .unwrap();
+    let quote = Quote::try_from_slice(&data).unwrap();
+    let offline = env.quoter(&mint).await.quote(LAMPORTS_PER_SOL, 10).unwrap();
+    assert_eq!(quote.spot_price, offline.spot_price);
+    assert_eq!(quote.buy, offline.buy);
+    assert_eq!(quote.sell, offline.sell);
+
+    let balance = env.token_balance(&user.pubkey(), &mint).await;
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    assert_eq!(
+        env.token_balance(&user.pubkey(), &mint).await,
+        balance + quote.buy.unwrap().amount_out
+    );
+}
+
+#[tokio::test]
+async fn get_quote_quotes_each_side_on_its_own() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    let supply = env
+        .account::<TokenInfo>(&pda::token_info(&mint).0)
+        .await
+        .total_supply;
+
+    // Selling more than the supply fails, which leaves the buy quote
+    let data = env
+        .view(instructions::get_quote(&mint, LAMPORTS_PER_SOL, supply + 1))
+        .await
+        .unwrap();
+    let quote = Quote::try_from_slice(&data).unwrap();
+    let offline = env.quoter(&mint).await.quote(LAMPORTS_PER_SOL, 0).unwrap();
+    assert_eq!(quote.buy, offline.buy);
+    assert!(quote.buy.is_some());
+    assert_eq!(quote.sell, None);
+
+    // And a buy past the max supply leaves the sell quote
+    env.edit(
+        &pda::bonding_curve(&mint).0,
+        |bonding_curve: &mut BondingCurve| bonding_curve.max_supply = supply + 1,
+    )
+    .await;
+    let data = env
+        .view(instructions::get_quote(&mint, LAMPORTS_PER_SOL, supply))
+        .await
+        .unwrap();
+    let quote = Quote::try_from_slice(&data).unwrap();
+    assert_eq!(quote.buy, None);
+    assert!(quote.sell.is_some());
+}
+
+#[tokio::test]
+async fn withdraw_fees_pays_receiver() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+
+    let pool = pda::liquidity_pool(&mint).0;
+    let receiver = Pubkey::new_unique();
+    let fees = LAMPORTS_PER_SOL / 100;
+    let pool_lamports = env.lamports(&pool).await;
+
+    let instruction = instructions::withdraw_fees(&creator.pubkey(), &mint, &receiver, fees);
+    env.send(&[instruction], &[&creator]).await.unwrap();
+
+    assert_eq!(env.lamports(&receiver).await, fees);
+    assert_eq!(env.lamports(&pool).await, pool_lamports - fees);
+    let liquidity_pool: LiquidityPool = env.account(&pool).await;
+    assert_eq!(liquidity_pool.accumulated_fees, 0);
+    assert_eq!(liquidity_pool.balance, LAMPORTS_PER_SOL - fees);
+}
+
+#[tokio::test]
+async fn withdraw_fees_rejects_invalid_withdrawals() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    let receiver = Pubkey::new_unique();
+    let fees = LAMPORTS_PER_SOL / 100;
+
+    let instruction = instructions::withdraw_fees(&user.pubkey(), &mint, &receiver, fees);
+    let result = env.send(&[instruction], &[&user]).await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let instruction = instructions::withdraw_fees(&creator.pubkey(), &mint, &receiver, fees + 1);
+    let result = env.send(&[instruction], &[&creator]).await;
+    assert_error(result, ErrorCode::InsufficientBalance);
+
+    // Recorded fees exceeding what the pool actually holds
+    let pool = pda::liquidity_pool(&mint).0;
+    let pool_lamports = env.lamports(&pool).await;
+    env.edit(&pool, |liquidity_pool: &mut LiquidityPool| {
+        liquidity_pool.accumulated_fees = pool_lamports + 1;
+    })
+    .await;
+    let instruction =
+        instructions::withdraw_fees(&creator.pubkey(), &mint, &receiver, pool_lamports + 1);
+    let result = env.send(&[instruction], &[&creator]).await;
+    assert_error(result, ErrorCode::ArithmeticUnderflow);
+}
+
+#[tokio::test]
+async fn trades_reject_invalid_amounts() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+
+    assert_error(env.buy(&user, &mint, 0).await, ErrorCode::InvalidMintAmount);
+
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    let balance = env.token_balance(&user.pubkey(), &mint).await;
+
+    assert_error(
+        env.sell(&user, &mint, 0).await,
+        ErrorCode::InvalidBurnAmount,
+    );
+    assert_error(
+        env.sell(&user, &mint, balance + 1).await,
+        ErrorCode::InsufficientBalance,
+    );
+}
+
+#[tokio::test]
+async fn sell_requires_pool_liquidity() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+
+    env.edit(
+        &pda::liquidity_pool(&mint).0,
+        |liquidity_pool: &mut LiquidityPool| liquidity_pool.balance = 0,
+    )
+    .await;
+    assert_error(
+        env.sell(&user, &mint, 1).await,
+        ErrorCode::InsufficientLiquidity,
+    );
+}
+
+#[tokio::test]
+async fn sell_rejects_amount_beyond_supply() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    let balance = env.token_balance(&user.pubkey(), &mint).await;
+
+    // The portfolio holds more than the curve has issued
+    env.edit(&pda::token_info(&mint).0, |token_info: &mut TokenInfo| {
+        token_info.total_supply = balance / 2
+    })
+    .await;
+    assert_error(
+        env.sell(&user, &mint, balance).await,
+        ErrorCode::InvalidBondingCurveParameters,
+    );
+}
+
+#[tokio::test]
+async fn trades_reject_unrepresentable_prices() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+
+    env.edit(&pda::token_info(&mint).0, |token_info: &mut TokenInfo| {
+        token_info.total_supply = u64::MAX
+    })
+    .await;
+    assert_error(
+        env.buy(&user, &mint, LAMPORTS_PER_SOL).await,
+        ErrorCode::ArithmeticOverflow,
+    );
+}
+
+#[tokio::test]
+async fn trades_reject_accounts_of_another_token() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let other = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+
+    let mut instruction = instructions::mint_token(&user.pubkey(), &mint, LAMPORTS_PER_SOL);
+    let bonding_curve = pda::bonding_curve(&mint).0;
+    for meta in &mut instruction.accounts {
+        if meta.pubkey == bonding_curve {
+            meta.pubkey = pda::bonding_curve(&other).0;
+        }
+    }
+    let instructions = [
+        instructions::create_token_account(&user.pubkey(), &user.pubkey(), &mint),
+        instruction,
+    ];
+    let result = env.send(&instructions, &[&user]).await;
+    assert_error(result, ErrorCode::InvalidToken);
+}
+
+#[tokio::test]
+async fn get_twap_averages_price_history() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+
+    let result = env.view(instructions::get_twap(&mint, 0)).await;
+    assert_error(result, ErrorCode::InvalidTwapWindow);
+
+    env.advance_clock(100).await;
+    let result = env.view(instructions::get_twap(&mint, 3_600)).await;
+    assert_error(result, ErrorCode::InsufficientPriceHistory);
+
+    let data = env.view(instructions::get_twap(&mint, 60)).await.unwrap();
+    let twap = u64::from_le_bytes(data.try_into().unwrap());
+    let bonding_curve: BondingCurve = env.account(&pda::bonding_curve(&mint).0).await;
+    assert_eq!(twap, bonding_curve.calculate_price(0).unwrap());
+}
+
+#[tokio::test]
+async fn admin_pauses_trading() {
+    let mut env = Env::new().await;
+    let admin = env.admin.insecure_clone();
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+
+    let result = env
+        .send(&[instructions::set_paused(&user.pubkey(), true)], &[&user])
+        .await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    env.send(
+        &[instructions::set_paused(&admin.pubkey(), true)],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+    assert_error(
+        env.buy(&user, &mint, LAMPORTS_PER_SOL).await,
+        ErrorCode::TradingPaused,
+    );
+
+    env.send(
+        &[instructions::set_paused(&admin.pubkey(), false)],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+}
+
+#[tokio::test]
+async fn admin_sets_fees() {
+    let mut env = Env::new().await;
+    let admin = env.admin.insecure_clone();
+    let user = env.user().await;
+
+    let result = env
+        .send(&[instructions::set_fees(&user.pubkey(), 50, 50)], &[&user])
+        .await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let result = env
+        .send(
+            &[instructions::set_fees(
+                &admin.pubkey(),
+                GlobalState::MAX_FEE_BPS + 1,
+                0,
+            )],
+            &[&admin],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidFee);
+
+    env.send(
+        &[instructions::set_fees(&admin.pubkey(), 250, 0)],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+    let global_state: GlobalState = env.account(&pda::global_state().0).await;
+    assert_eq!(global_state.buy_fee_bps, 250);
+    assert_eq!(global_state.sell_fee_bps, 0);
+
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    let liquidity_pool: LiquidityPool = env.account(&pda::liquidity_pool(&mint).0).await;
+    assert_eq!(liquidity_pool.accumulated_fees, LAMPORTS_PER_SOL / 40);
+}
+
+#[tokio::test]
+async fn admin_sets_trade_limits() {
+    let mut env = Env::new().await;
+    let admin = env.admin.insecure_clone();
+    let user = env.user().await;
+
+    let limits = |signer: &Keypair| {
+        instructions::set_trade_limits(
+            &signer.pubkey(),
+            2 * LAMPORTS_PER_SOL,
+            50,
+            3 * LAMPORTS_PER_SOL,
+        )
+    };
+    assert_error(
+        env.send(&[limits(&user)], &[&user]).await,
+        ErrorCode::Unauthorized,
+    );
+    env.send(&[limits(&admin)], &[&admin]).await.unwrap();
+
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    env.set_slot(1_000).await;
+    assert_error(
+        env.buy(&user, &mint, 3 * LAMPORTS_PER_SOL).await,
+        ErrorCode::BuyLimitExceeded,
+    );
+    env.buy(&user, &mint, 2 * LAMPORTS_PER_SOL).await.unwrap();
+    assert_error(
+        env.sell(&user, &mint, 51).await,
+        ErrorCode::SellLimitExceeded,
+    );
+
+    // The pool has seen 2 SOL of its 3 SOL this slot
+    let other = env.user().await;
+    assert_error(
+        env.buy(&other, &mint, 2 * LAMPORTS_PER_SOL).await,
+        ErrorCode::SlotVolumeExceeded,
+    );
+    env.set_slot(1_001).await;
+    env.buy(&other, &mint, 2 * LAMPORTS_PER_SOL).await.unwrap();
+    env.sell(&user, &mint, 50).await.unwrap();
+
+    // Zero lifts the limits
+    env.send(
+        &[instructions::set_trade_limits(&admin.pubkey(), 0, 0, 0)],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+    env.buy(&other, &mint, 3 * LAMPORTS_PER_SOL).await.unwrap();
+}
+
+#[tokio::test]
+async fn verify_pool_reports_reserves() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    let balance = env.token_balance(&user.pubkey(), &mint).await;
+    env.sell(&user, &mint, balance / 2).await.unwrap();
+
+    let data = env.view(instructions::verify_pool(&mint)).await.unwrap();
+    let report = PoolReport::try_from_slice(&data).unwrap();
+    let pool = pda::liquidity_pool(&mint).0;
+    let liquidity_pool: LiquidityPool = env.account(&pool).await;
+    let token_info: TokenInfo = env.account(&pda::token_info(&mint).0).await;
+    assert!(report.solvent);
+    assert_eq!(report.total_supply, token_info.total_supply);
+    assert_eq!(report.balance, liquidity_pool.balance);
+    assert_eq!(report.accumulated_fees, liquidity_pool.accumulated_fees);
+    assert_eq!(report.lamports, env.lamports(&pool).await);
+    assert!(report.required_reserves > 0);
+    assert!(report.required_reserves <= report.balance);
+    assert_eq!(
+        report.lamports,
+        report.balance + report.accumulated_fees + report.rent_exempt_minimum
+    );
+
+    // Anyone may verify a pool, even with trading paused
+    let admin = env.admin.insecure_clone();
+    env.send(
+        &[instructions::set_paused(&admin.pubkey(), true)],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+    env.view(instructions::verify_pool(&mint)).await.unwrap();
+}
+
+#[tokio::test]
+async fn trades_refuse_to_leave_pools_insolvent() {
+    let mut env = Env::new().await;
+    let creator = env.user().await;
+    let mint = env.create_token(&creator).await.unwrap();
+    let user = env.user().await;
+    env.buy(&user, &mint, LAMPORTS_PER_SOL).await.unwrap();
+    let pool = pda::liquidity_pool(&mint).0;
+    let report = |data: Vec<u8>| PoolReport::try_from_slice(&data).unwrap();
+
+    // Recorded reserves short of what the supply can be sold back for
+    let healthy: LiquidityPool = env.account(&pool).await;
+    let required =
+        report(env.view(instructions::verify_pool(&mint)).await.unwrap()).required_reserves;
+    env.edit(&pool, |liquidity_pool: &mut LiquidityPool| {
+        liquidity_pool.balance = required / 2;
+    })
+    .await;
+    assert!(!report(env.view(instructions::verify_pool(&mint)).await.unwrap()).solvent);
+    assert_error(
+        env.buy(&user, &mint, LAMPORTS_PER_SOL).await,
+        ErrorCode::PoolInsolvent,
+    );
+    assert_error(env.sell(&user, &mint, 1).await, ErrorCode::PoolInsolvent);
+
+    // Recorded fees the pool does not actually hold
+    env.edit(&pool, |liquidity_pool: &mut LiquidityPool| {
+        liquidity_pool.balance = healthy.balance;
+        liquidity_pool.accumulated_fees = healthy.accumulated_fees + 1;
+    })
+    .await;
+    assert!(!report(env.view(instructions::verify_pool(&mint)).await.unwrap()).solvent);
+    assert_error(env.sell(&user, &mint, 1).await, ErrorCode::PoolInsolvent);
+}
@@ -0,0 +1,382 @@
+//! Stateful fuzzing of the trade instructions on the in-process SVM.
+//!
+//! Random sequences of `create_token` (with or without an initial buy and a creator allocation),
+//! `mint_token`, `burn_token`, `withdraw_fees` and `claim_vested` run with random signers,
+//! tokens, amounts and occasionally another token's curve accounts. Any instruction may fail,
+//! but none may panic, which surfaces as a transport error of the banks client. After every step
+//! each pool must stay solvent:
+//!
+//! - its lamports cover `balance + accumulated_fees` on top of its rent-exempt reserve
+//! - `balance` covers selling back the whole supply, creator allocation included, along the curve
+//! - the mint supply, `total_supply` and the holders' portfolios plus the vesting account agree
+//!
+//! A run tries 32 sequences by default; set `FAME_FUZZ_CASES` for a longer session, e.g.
+//! `FAME_FUZZ_CASES=2000 cargo test -p fame --test fuzz`. Failing sequences are shrunk and
+//! saved to `fuzz.proptest-regressions`, which is replayed first on the next run.
+
+mod common;
+
+use std::cell::RefCell;
+
+use anchor_spl::token::{Mint, TokenAccount};
+use common::{Env, TransactionResult};
+use fame::instructions::calculate_sol_to_return;
+use fame::state::{BondingCurve, LiquidityPool, TokenInfo, UserPortfolio};
+use fame_client::instructions::{CreateTokenArgs, CreatorAllocation};
+use fame_client::{instructions, pda};
+use proptest::prelude::*;
+use proptest::test_runner::{FileFailurePersistence, TestCaseError, TestRunner};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+const ACTORS: usize = 4;
+const MAX_TOKENS: usize = 4;
+
+#[derive(Clone, Debug)]
+enum Action {
+    CreateToken {
+        creator: usize,
+        initial_buy_sol: Option<u64>,
+        allocation_bps: Option<u16>,
+    },
+    Buy {
+        user: usize,
+        token: usize,
+        amount_sol: u64,
+        foreign_curve: Option<usize>,
+    },
+    /// Sells this share, in basis points, of the user's holding; above 10 000 it oversells.
+    Sell {
+        user: usize,
+        token: usize,
+        share_bps: u16,
+        foreign_curve: Option<usize>,
+    },
+    /// Claims the vested allocation after letting this many seconds pass.
+    ClaimVested {
+        signer: usize,
+        token: usize,
+        wait_seconds: i64,
+    },
+    /// Withdraws this share, in basis points, of the accumulated fees.
+    WithdrawFees {
+        signer: usize,
+        token: usize,
+        share_bps: u16,
+    },
+}
+
+fn amount_sol() -> impl Strategy<Value = u64> {
+    prop_oneof![
+        4 => 1..=20 * LAMPORTS_PER_SOL,
+        1 => any::<u64>(),
+    ]
+}
+
+fn foreign_curve() -> impl Strategy<Value = Option<usize>> {
+    prop::option::weighted(0.1, 0..MAX_TOKENS)
+}
+
+fn action() -> impl Strategy<Value = Action> {
+    prop_oneof![
+        1 => (
+            0..ACTORS,
+            prop::option::weighted(0.3, amount_sol()),
+            prop::option::weighted(0.3, 0..=1_200u16),
+        )
+            .prop_map(|(creator, initial_buy_sol, allocation_bps)| Action::CreateToken {
+                creator,
+                initial_buy_sol,
+                allocation_bps,
+            }),
+        4 => (0..ACTORS, 0..MAX_TOKENS, amount_sol(), foreign_curve()).prop_map(
+            |(user, token, amount_sol, foreign_curve)| Action::Buy {
+                user,
+                token,
+                amount_sol,
+                foreign_curve,
+            }
+        ),
+        3 => (0..ACTORS, 0..MAX_TOKENS, 0..=12_000u16, foreign_curve()).prop_map(
+            |(user, token, share_bps, foreign_curve)| Action::Sell {
+                user,
+                token,
+                share_bps,
+                foreign_curve,
+            }
+        ),
+        1 => (0..ACTORS, 0..MAX_TOKENS, 0..=120i64).prop_map(|(signer, token, wait_seconds)| {
+            Action::ClaimVested {
+                signer,
+                token,
+                wait_seconds,
+            }
+        }),
+        1 => (0..ACTORS, 0..MAX_TOKENS, 0..=12_000u16).prop_map(|(signer, token, share_bps)| {
+            Action::WithdrawFees {
+                signer,
+                token,
+                share_bps,
+            }
+        }),
+    ]
+}
+
+fn config() -> ProptestConfig {
+    let cases = std::env::var("FAME_FUZZ_CASES")
+        .ok()
+        .and_then(|cases| cases.parse().ok())
+        .unwrap_or(32);
+    ProptestConfig {
+        cases,
+        source_file: Some(file!()),
+        failure_persistence: Some(Box::new(FileFailurePersistence::WithSource(
+            "proptest-regressions",
+        ))),
+        // Every shrink step replays a whole sequence on a fresh SVM
+        max_shrink_iters: 64,
+        ..ProptestConfig::default()
+    }
+}
+
+fn share(amount: u64, share_bps: u16) -> u64 {
+    (amount as u128 * share_bps as u128 / 10_000) as u64
+}
+
+/// Points the curve accounts of a trade at another token's.
+fn use_foreign_curve(instruction: &mut Instruction, mint: &Pubkey, foreign: &Pubkey) {
+    for meta in &mut instruction.accounts {
+        if meta.pubkey == pda::bonding_curve(mint).0 {
+            meta.pubkey = pda::bonding_curve(foreign).0;
+        } else if meta.pubkey == pda::liquidity_pool(mint).0 {
+            meta.pubkey = pda::liquidity_pool(foreign).0;
+        }
+    }
+}
+
+/// One sequence of actions, with its own actors and tokens on the shared SVM.
+struct Fuzzer<'a> {
+    env: &'a mut Env,
+    actors: Vec<Keypair>,
+    mints: Vec<Pubkey>,
+}
+
+impl<'a> Fuzzer<'a> {
+    async fn new(env: &'a mut Env) -> Self {
+        let mut actors = Vec::new();
+        for _ in 0..ACTORS {
+            let actor = Keypair::new();
+            // Enough to pay for the largest creator allocation and still trade
+            env.fund(&actor.pubkey(), 1_000 * LAMPORTS_PER_SOL).await;
+            actors.push(actor);
+        }
+        let mut fuzzer = Self {
+            env,
+            actors,
+            mints: Vec::new(),
+        };
+        // Start with one token so early trades have something to hit
+        fuzzer
+            .apply(&Action::CreateToken {
+                creator: 0,
+                initial_buy_sol: None,
+                allocation_bps: None,
+            })
+            .await;
+        fuzzer
+    }
+
+    fn mint(&self, token: usize) -> Option<Pubkey> {
+        (!self.mints.is_empty()).then(|| self.mints[token % self.mints.len()])
+    }
+
+    async fn apply(&mut self, action: &Action) -> Option<TransactionResult<()>> {
+        let result = match *action {
+            Action::CreateToken {
+                creator,
+                initial_buy_sol,
+                allocation_bps,
+            } => {
+                if self.mints.len() == MAX_TOKENS {
+                    return None;
+                }
+                let creator = self.actors[creator].insecure_clone();
+                let mint = Keypair::new();
+                let args = CreateTokenArgs {
+                    name: "Fame".to_string(),
+                    symbol: "FAME".to_string(),
+                    initial_buy_sol,
+                    creator_allocation: allocation_bps.map(|supply_bps| CreatorAllocation {
+                        supply_bps,
+                        cliff_seconds: 10,
+                        duration_seconds: 100,
+                    }),
+                    ..CreateTokenArgs::default()
+                };
+                let instruction =
+                    instructions::create_token(&creator.pubkey(), &mint.pubkey(), args);
+                let result = self.send_signed(&[instruction], &[&creator, &mint]).await;
+                result.map(|()| self.mints.push(mint.pubkey()))
+            }
+            Action::Buy {
+                user,
+                token,
+                amount_sol,
+                foreign_curve,
+            } => {
+                let mint = self.mint(token)?;
+                let user = self.actors[user].insecure_clone();
+                let mut instruction = instructions::mint_token(&user.pubkey(), &mint, amount_sol);
+                if let Some(foreign) = foreign_curve.and_then(|token| self.mint(token)) {
+                    use_foreign_curve(&mut instruction, &mint, &foreign);
+                }
+                let instructions = [
+                    instructions::create_token_account(&user.pubkey(), &user.pubkey(), &mint),
+                    instruction,
+                ];
+                self.send(&instructions, &user).await
+            }
+            Action::Sell {
+                user,
+                token,
+                share_bps,
+                foreign_curve,
+            } => {
+                let mint = self.mint(token)?;
+                let user = self.actors[user].insecure_clone();
+                let portfolio: Option<UserPortfolio> = self
+                    .env
+                    .try_account(&pda::user_portfolio(&user.pubkey(), &mint).0)
+                    .await;
+                let held = portfolio.map_or(0, |portfolio| portfolio.balance);
+                let mut instruction =
+                    instructions::burn_token(&user.pubkey(), &mint, share(held, share_bps));
+                if let Some(foreign) = foreign_curve.and_then(|token| self.mint(token)) {
+                    use_foreign_curve(&mut instruction, &mint, &foreign);
+                }
+                self.send(&[instruction], &user).await
+            }
+            Action::ClaimVested {
+                signer,
+                token,
+                wait_seconds,
+            } => {
+                let mint = self.mint(token)?;
+                let signer = self.actors[signer].insecure_clone();
+                self.env.advance_clock(wait_seconds).await;
+                let instruction = instructions::claim_vested(&signer.pubkey(), &mint);
+                self.send(&[instruction], &signer).await
+            }
+            Action::WithdrawFees {
+                signer,
+                token,
+                share_bps,
+            } => {
+                let mint = self.mint(token)?;
+                let signer = self.actors[signer].insecure_clone();
+                let liquidity_pool: LiquidityPool =
+                    self.env.account(&pda::liquidity_pool(&mint).0).await;
+                let amount = share(liquidity_pool.accumulated_fees, share_bps);
+                let instruction =
+                    instructions::withdraw_fees(&signer.pubkey(), &mint, &signer.pubkey(), amount);
+                self.send(&[instruction], &signer).await
+            }
+        };
+        Some(result)
+    }
+
+    /// Sends with the test payer covering transaction fees, so actors that spent all their
+    /// lamports still get their transactions processed and rejected by the program.
+    async fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signer: &Keypair,
+    ) -> TransactionResult<()> {
+        self.send_signed(instructions, &[signer]).await
+    }
+
+    async fn send_signed(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> TransactionResult<()> {
+        let payer = self.env.context.payer.insecure_clone();
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+        self.env.send(instructions, &all_signers).await
+    }
+
+    async fn check_invariants(&mut self) -> Result<(), TestCaseError> {
+        for mint in self.mints.clone() {
+            let pool = pda::liquidity_pool(&mint).0;
+            let liquidity_pool: LiquidityPool = self.env.account(&pool).await;
+            let lamports = self.env.lamports(&pool).await;
+            // On top of its rent-exempt reserve, which no trade may eat into
+            let rent = self.env.context.banks_client.get_rent().await.unwrap();
+            let recorded = liquidity_pool.balance as u128
+                + liquidity_pool.accumulated_fees as u128
+                + rent.minimum_balance(LiquidityPool::LEN) as u128;
+            prop_assert!(
+                lamports as u128 >= recorded,
+                "pool of {mint} holds {lamports} lamports for {recorded} recorded"
+            );
+
+            let token_info: TokenInfo = self.env.account(&pda::token_info(&mint).0).await;
+            let bonding_curve: BondingCurve = self.env.account(&pda::bonding_curve(&mint).0).await;
+            let supply = token_info.total_supply;
+            let owed = calculate_sol_to_return(&bonding_curve, supply, supply)
+                .map_err(|err| TestCaseError::fail(format!("pricing supply {supply}: {err}")))?;
+            prop_assert!(
+                owed <= liquidity_pool.balance,
+                "pool of {mint} owes {owed} for supply {supply}, holds {}",
+                liquidity_pool.balance
+            );
+
+            let mint_account: Mint = self.env.account(&mint).await;
+            prop_assert_eq!(mint_account.supply, supply);
+            let vesting_account = pda::token_account(&pda::vesting(&mint).0, &mint);
+            let vesting: Option<TokenAccount> = self.env.try_account(&vesting_account).await;
+            let mut held = vesting.map_or(0, |vesting| vesting.amount);
+            for actor in &self.actors {
+                let portfolio: Option<UserPortfolio> = self
+                    .env
+                    .try_account(&pda::user_portfolio(&actor.pubkey(), &mint).0)
+                    .await;
+                held += portfolio.map_or(0, |portfolio| portfolio.balance);
+            }
+            prop_assert_eq!(held, supply);
+        }
+        Ok(())
+    }
+}
+
+async fn run(env: &mut Env, actions: Vec<Action>) -> Result<(), TestCaseError> {
+    let mut fuzzer = Fuzzer::new(env).await;
+    fuzzer.check_invariants().await?;
+    for action in &actions {
+        // Instruction errors are expected; a panicking program fails inside `Env::send`
+        fuzzer.apply(action).await;
+        fuzzer.check_invariants().await?;
+    }
+    Ok(())
+}
+
+#[test]
+fn random_trades_keep_pools_solvent() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    // A fresh SVM per sequence leaks its background threads, so all sequences share one
+    let env = RefCell::new(runtime.block_on(Env::new()));
+
+    let mut runner = TestRunner::new(config());
+    let result = runner.run(&prop::collection::vec(action(), 1..32), |actions| {
+        runtime.block_on(run(&mut env.borrow_mut(), actions))
+    });
+    if let Err(err) = result {
+        panic!("{err}");
+    }
+}
@@ -353,7 +353,7 @@ describe("fame", () => {
         };
 
         const builder = program.methods
-            .createToken(args.name, args.symbol, args.socialAccountUrl)
+            .createToken(args.name, args.symbol, args.socialAccountUrl, null, null, null, null, null, null)
             .accounts(accounts)
             .signers([args.admin]);
 
@@ -378,6 +378,7 @@ describe("fame", () => {
         );
         const accounts = {
             user: args.user.publicKey,
+            recipient: args.user.publicKey,
             tokenInfo: anchor.web3.PublicKey.findProgramAddressSync(
                 [Buffer.from("token_info"), token1.mint.publicKey.toBuffer()],
                 program.programId,
@@ -410,7 +411,7 @@ describe("fame", () => {
             systemProgram: anchor.web3.SystemProgram.programId,
         };
         const builder = program.methods
-            .mintToken(args.amount)
+            .mintToken(args.amount, [])
             .accounts(accounts)
             .signers([args.user]);
 